@@ -0,0 +1,103 @@
+
+use std::f64::consts::PI;
+
+use universe::{BlackHole, GRAVITATIONAL_CONSTANT, PROTON_MASS, SPEED_OF_LIGHT, THOMSON_CROSS_SECTION};
+use universe::circular_orbit::OrbitDirection;
+
+/// Accretion physics: how bright a black hole can shine by swallowing matter and
+/// how much of the rest mass of the accreted matter is turned into radiation.
+///
+/// All methods return None if the mass is unknown.
+impl BlackHole {
+
+    /// Calculates the Eddington luminosity in Watts, the luminosity at which the radiation
+    /// pressure on ionized hydrogen balances gravity.
+    /// See: https://en.wikipedia.org/wiki/Eddington_luminosity
+    ///
+    ///        4 * pi * G * M * mp * c
+    /// LEdd = -----------------------
+    ///                sigmaT
+    ///
+    pub fn eddington_luminosity(&self) -> Option<f64> {
+        self.mass.map(|mass| 4.0 * PI * GRAVITATIONAL_CONSTANT * mass * PROTON_MASS * SPEED_OF_LIGHT
+            / THOMSON_CROSS_SECTION)
+    }
+
+    /// Calculates the radiative efficiency of a Novikov-Thorne thin disk, which ends at the
+    /// innermost stable circular orbit. It is the binding energy of the ISCO, ranging from
+    /// 3.8% (maximal retrograde spin) over 5.7% (no spin) to 42% (maximal prograde spin).
+    /// Returns None if the spin exceeds the maximal value.
+    ///
+    ///                      2 * rg
+    /// eta = 1 - sqrt(1 - ---------)
+    ///                    3 * rISCO
+    ///
+    pub fn radiative_efficiency(&self, direction: OrbitDirection) -> Option<f64> {
+        let rg = self.gravitational_radius()?;
+        let isco = self.isco_radius(direction)?;
+
+        Some(1.0 - (1.0 - 2.0 * rg / (3.0 * isco)).sqrt())
+    }
+
+    /// Calculates the mass accretion rate in kg/s which produces the Eddington luminosity
+    ///
+    ///            LEdd
+    /// MEdd = ---------
+    ///        eta * c^2
+    ///
+    pub fn eddington_accretion_rate(&self, direction: OrbitDirection) -> Option<f64> {
+        Some(self.eddington_luminosity()?
+            / (self.radiative_efficiency(direction)? * SPEED_OF_LIGHT.powi(2)))
+    }
+
+    /// Converts an observed bolometric luminosity in Watts into the Eddington ratio
+    pub fn eddington_ratio(&self, bolometric_luminosity: f64) -> Option<f64> {
+        self.eddington_luminosity().map(|eddington| bolometric_luminosity / eddington)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, Type};
+
+    fn quasar(spin: f64) -> BlackHole {
+        let mass = 1.0e9 * SOLAR_MASS;
+        let rg = BlackHoleBuilder::new("3C 273").mass(mass).build().gravitational_radius().unwrap();
+
+        BlackHoleBuilder::new("3C 273")
+            .mass(mass)
+            .angular_momentum(spin * rg * mass * SPEED_OF_LIGHT)
+            .classification(Type::SuperMassive)
+            .build()
+    }
+
+    #[test]
+    fn eddington_limit() {
+
+        let black_hole = quasar(0.0);
+        let luminosity = black_hole.eddington_luminosity().unwrap();
+
+        assert!((luminosity / 1.0e9 / 1.257e31 - 1.0).abs() < 1e-3);
+        assert_eq!(Some(0.5), black_hole.eddington_ratio(luminosity / 2.0));
+
+        // roughly 40 solar masses per year
+        let rate = black_hole.eddington_accretion_rate(OrbitDirection::Prograde).unwrap();
+        assert!((rate * 365.25 * 86_400.0 / SOLAR_MASS - 38.6).abs() < 0.5);
+    }
+
+    #[test]
+    fn radiative_efficiency_depends_on_spin() {
+
+        let efficiency = |spin: f64, direction| quasar(spin).radiative_efficiency(direction).unwrap();
+
+        // Novikov & Thorne (1973): 1 - sqrt(8/9) for Schwarzschild, 1 - 1/sqrt(3) for an
+        // extremal prograde disk and 1 - sqrt(25/27) for an extremal retrograde disk
+        assert!((efficiency(1.0 - 1e-12, OrbitDirection::Prograde) - 0.422_6).abs() < 1e-3);
+        assert!((efficiency(1.0 - 1e-12, OrbitDirection::Retrograde) - 0.037_8).abs() < 1e-4);
+        assert!((efficiency(0.0, OrbitDirection::Prograde) - 0.057_2).abs() < 1e-4);
+        assert!((efficiency(0.998, OrbitDirection::Prograde) - 0.321).abs() < 1e-3);
+        assert!((efficiency(0.998, OrbitDirection::Retrograde) - 0.038).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,220 @@
+
+use universe::{BlackHole, BlackHoleBuilder, GRAVITATIONAL_CONSTANT, SPEED_OF_LIGHT, Type};
+use universe::circular_orbit::{isco_radius_for_spin, OrbitDirection};
+
+// Fit of the final spin of aligned-spin mergers by Rezzolla et al. (2008), ApJ 674, L29
+const SPIN_FIT_S4: f64 = -0.1229;
+const SPIN_FIT_S5: f64 = 0.4537;
+const SPIN_FIT_T0: f64 = -2.8904;
+const SPIN_FIT_T2: f64 = -3.5171;
+const SPIN_FIT_T3: f64 = 2.5763;
+
+// Fit of the radiated energy by Barausse, Morozova & Rezzolla (2012), ApJ 758, 63
+const ENERGY_FIT_P0: f64 = 0.04827;
+const ENERGY_FIT_P1: f64 = 0.01707;
+
+// Fit of the recoil of non-spinning mergers by Gonzalez et al. (2007), PRL 98, 091101
+const KICK_FIT_A: f64 = 1.2e7; // m/s
+const KICK_FIT_B: f64 = -0.93;
+
+/// Two black holes orbiting each other on a (quasi) circular orbit until they merge.
+///
+/// The spins of both black holes are assumed to be aligned with the orbital angular
+/// momentum (a negative angular momentum means anti-aligned). All methods return None
+/// if the mass of one of the black holes is unknown.
+#[derive(Debug, PartialEq)]
+pub struct BinaryBlackHole {
+
+    /// The first black hole
+    pub primary: BlackHole,
+
+    /// The second black hole
+    pub secondary: BlackHole
+}
+
+impl BinaryBlackHole {
+
+    /// Pairs two black holes
+    pub fn new(primary: BlackHole, secondary: BlackHole) -> BinaryBlackHole {
+        BinaryBlackHole {
+            primary,
+            secondary
+        }
+    }
+
+    /// Calculates the total mass
+    pub fn total_mass(&self) -> Option<f64> {
+        Some(self.primary.mass? + self.secondary.mass?)
+    }
+
+    /// Calculates the chirp mass, which determines how fast the frequency of the emitted
+    /// gravitational waves increases
+    /// See: https://en.wikipedia.org/wiki/Chirp_mass
+    ///
+    ///      (m1 * m2)^(3/5)
+    /// Mc = ---------------
+    ///       (m1 + m2)^(1/5)
+    ///
+    pub fn chirp_mass(&self) -> Option<f64> {
+        Some((self.primary.mass? * self.secondary.mass?).powf(0.6) / self.total_mass()?.powf(0.2))
+    }
+
+    /// Calculates the symmetric mass ratio, which is 1/4 for equal masses and approaches
+    /// 0 for extreme mass ratios
+    ///
+    ///          m1 * m2
+    /// eta = -------------
+    ///       (m1 + m2)^2
+    ///
+    pub fn symmetric_mass_ratio(&self) -> Option<f64> {
+        Some(self.primary.mass? * self.secondary.mass? / self.total_mass()?.powi(2))
+    }
+
+    /// Calculates the time in seconds until the black holes merge, starting at the given
+    /// orbital separation (in meters), as gravitational waves carry away the orbital energy.
+    /// See: Peters (1964), Phys. Rev. 136, B1224
+    ///
+    ///            5 * c^5 * a^4
+    /// t = -------------------------
+    ///     256 * G^3 * m1 * m2 * M
+    ///
+    pub fn time_to_merger(&self, separation: f64) -> Option<f64> {
+        Some(5.0 * SPEED_OF_LIGHT.powi(5) * separation.powi(4)
+            / (256.0 * GRAVITATIONAL_CONSTANT.powi(3) * self.primary.mass? * self.secondary.mass?
+                * self.total_mass()?))
+    }
+
+    /// Estimates the mass of the remnant black hole from numerical-relativity fits. The
+    /// difference to the total mass is radiated away as gravitational waves (about 5% for
+    /// two non-spinning black holes of equal mass).
+    pub fn remnant_mass(&self) -> Option<f64> {
+        let eta = self.symmetric_mass_ratio()?;
+        let (large_spin, small_spin, ratio) = self.spins_and_mass_ratio()?;
+
+        let spin = (large_spin + small_spin * ratio.powi(2)) / (1.0 + ratio).powi(2);
+        let direction = if spin < 0.0 { OrbitDirection::Retrograde } else { OrbitDirection::Prograde };
+        let isco_energy = (1.0 - 2.0 / (3.0 * isco_radius_for_spin(spin.abs(), direction))).sqrt();
+
+        let radiated = eta * (1.0 - 4.0 * eta) * (1.0 - isco_energy)
+            + 16.0 * eta.powi(2) * (ENERGY_FIT_P0 + 4.0 * ENERGY_FIT_P1 * spin * (spin + 1.0));
+
+        Some(self.total_mass()? * (1.0 - radiated))
+    }
+
+    /// Estimates the dimensionless spin of the remnant black hole from numerical-relativity
+    /// fits (about 0.69 for two non-spinning black holes of equal mass)
+    pub fn remnant_spin(&self) -> Option<f64> {
+        let eta = self.symmetric_mass_ratio()?;
+        let (large_spin, small_spin, ratio) = self.spins_and_mass_ratio()?;
+
+        let spin = (large_spin + small_spin * ratio.powi(2)) / (1.0 + ratio.powi(2));
+
+        Some(spin
+            + spin * eta * (SPIN_FIT_S4 * spin + SPIN_FIT_S5 * eta + SPIN_FIT_T0)
+            + eta * (2.0 * 3.0_f64.sqrt() + SPIN_FIT_T2 * eta + SPIN_FIT_T3 * eta.powi(2)))
+    }
+
+    /// Estimates the recoil velocity (kick) of the remnant black hole in m/s caused by the
+    /// asymmetric emission of gravitational waves. The contribution of the spins is
+    /// neglected, so equal masses lead to no recoil at all.
+    ///
+    /// v = A * eta^2 * sqrt(1 - 4 * eta) * (1 + B * eta)
+    ///
+    pub fn recoil_velocity(&self) -> Option<f64> {
+        let eta = self.symmetric_mass_ratio()?;
+
+        Some(KICK_FIT_A * eta.powi(2) * (1.0 - 4.0 * eta).max(0.0).sqrt() * (1.0 + KICK_FIT_B * eta))
+    }
+
+    /// Creates a builder for the remnant black hole with the given name. Mass, angular
+    /// momentum, electric charge and classification are already set; the discoverer and
+    /// the year of discovery fall back to the defaults unless they are set on the builder.
+    pub fn remnant_builder(&self, name: &str) -> Option<BlackHoleBuilder> {
+        let mass = self.remnant_mass()?;
+        let spin = self.remnant_spin()?;
+        let angular_momentum = spin * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT;
+        let electric_charge = match (self.primary.electric_charge, self.secondary.electric_charge) {
+            (None, None) => None,
+            (first, second) => Some(first.unwrap_or(0.0) + second.unwrap_or(0.0))
+        };
+
+        Some(BlackHoleBuilder::new(name)
+            .mass(mass)
+            .angular_momentum(angular_momentum)
+            .electric_charge(electric_charge)
+            .classification(Type::from_mass(mass)))
+    }
+
+    /// Merges both black holes into a remnant black hole with the given name
+    pub fn remnant(&self, name: &str) -> Option<BlackHole> {
+        self.remnant_builder(name).map(|builder| builder.build())
+    }
+
+    /// The dimensionless spins of the heavier and the lighter black hole together with the
+    /// mass ratio (lighter / heavier)
+    fn spins_and_mass_ratio(&self) -> Option<(f64, f64, f64)> {
+        let (large, small) = if self.primary.mass? >= self.secondary.mass? {
+            (&self.primary, &self.secondary)
+        } else {
+            (&self.secondary, &self.primary)
+        };
+        let large_spin = large.dimensionless_spin()?;
+        let small_spin = small.dimensionless_spin()?;
+
+        if large_spin.abs() > 1.0 || small_spin.abs() > 1.0 {
+            return None;
+        }
+        Some((large_spin, small_spin, small.mass? / large.mass?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{DEFAULT_DISCOVERED_BY, DEFAULT_DISCOVERED_YEAR, SOLAR_MASS};
+
+    fn stellar(name: &str, solar_masses: f64) -> BlackHole {
+        BlackHoleBuilder::new(name)
+            .mass(solar_masses * SOLAR_MASS)
+            .classification(Type::Stellar)
+            .build()
+    }
+
+    #[test]
+    fn equal_mass_merger() {
+
+        let binary = BinaryBlackHole::new(stellar("A", 30.0), stellar("B", 30.0));
+        let remnant = binary.remnant("GW150914").unwrap();
+
+        assert_eq!(Some(0.25), binary.symmetric_mass_ratio());
+        assert!((binary.chirp_mass().unwrap() / SOLAR_MASS - 26.12).abs() < 0.01);
+        assert!((remnant.mass.unwrap() / binary.total_mass().unwrap() - 0.9517).abs() < 1e-3);
+        assert!((remnant.dimensionless_spin().unwrap() - 0.6865).abs() < 1e-3);
+        assert!(binary.recoil_velocity().unwrap().abs() < 1e-6);
+
+        assert_eq!("GW150914", remnant.name);
+        assert_eq!(DEFAULT_DISCOVERED_BY, remnant.discovered_by);
+        assert_eq!(DEFAULT_DISCOVERED_YEAR, remnant.year_of_discovery);
+        assert_eq!(Some(Type::Stellar), remnant.classification);
+    }
+
+    #[test]
+    fn unequal_mass_merger() {
+
+        let binary = BinaryBlackHole::new(stellar("A", 10.0), stellar("B", 30.0));
+        let remnant = binary.remnant_builder("Remnant").unwrap()
+            .discovered_by("LIGO".to_string())
+            .year_of_discovery(2017)
+            .build();
+
+        // the maximal recoil of about 175 km/s occurs at a mass ratio of about 1/3
+        assert!((binary.recoil_velocity().unwrap() / 1000.0 - 174.0).abs() < 2.0);
+        assert!(remnant.dimensionless_spin().unwrap() < 0.6865);
+        assert_eq!("LIGO", remnant.discovered_by);
+        assert_eq!(2017, remnant.year_of_discovery);
+
+        let time = binary.time_to_merger(1.0e6).unwrap();
+        assert!((binary.time_to_merger(2.0e6).unwrap() / time - 16.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,135 @@
+
+use universe::BlackHole;
+
+/// The direction of a circular orbit relative to the rotation of the black hole
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OrbitDirection {
+    Prograde,
+    Retrograde
+}
+
+impl OrbitDirection {
+
+    /// +1 for co-rotating and -1 for counter-rotating orbits
+    pub fn sign(self) -> f64 {
+        match self {
+            OrbitDirection::Prograde => 1.0,
+            OrbitDirection::Retrograde => -1.0
+        }
+    }
+}
+
+/// Calculates the radius of the innermost stable circular orbit in units of the
+/// gravitational radius for the given dimensionless spin (between 0 and 1)
+pub fn isco_radius_for_spin(spin: f64, direction: OrbitDirection) -> f64 {
+    let z1 = 1.0 + (1.0 - spin.powi(2)).cbrt() * ((1.0 + spin).cbrt() + (1.0 - spin).cbrt());
+    let z2 = (3.0 * spin.powi(2) + z1.powi(2)).sqrt();
+    let root = ((3.0 - z1) * (3.0 + z1 + 2.0 * z2)).max(0.0).sqrt();
+
+    3.0 + z2 - direction.sign() * root
+}
+
+/// The characteristic circular orbits in the equatorial plane of a Kerr black hole.
+/// The electric charge is not taken into account.
+///
+/// All methods return None if the mass is unknown or the spin exceeds the
+/// maximal (extremal) value.
+impl BlackHole {
+
+    /// Calculates the radius of the innermost stable circular orbit (ISCO).
+    /// See: https://en.wikipedia.org/wiki/Innermost_stable_circular_orbit
+    ///
+    /// Z1 = 1 + (1 - X^2)^(1/3) * ((1 + X)^(1/3) + (1 - X)^(1/3))
+    /// Z2 = sqrt(3 * X^2 + Z1^2)
+    /// r  = rg * (3 + Z2 -/+ sqrt((3 - Z1) * (3 + Z1 + 2 * Z2)))
+    ///
+    pub fn isco_radius(&self, direction: OrbitDirection) -> Option<f64> {
+        let rg = self.gravitational_radius()?;
+        let spin = self.bounded_spin()?;
+
+        Some(rg * isco_radius_for_spin(spin, direction))
+    }
+
+    /// Calculates the radius of the circular photon orbit in the equatorial plane.
+    /// See: https://en.wikipedia.org/wiki/Photon_sphere
+    ///
+    /// r = 2 * rg * (1 + cos(2/3 * acos(-/+ X)))
+    ///
+    pub fn photon_orbit_radius(&self, direction: OrbitDirection) -> Option<f64> {
+        let rg = self.gravitational_radius()?;
+        let spin = self.bounded_spin()?;
+
+        Some(2.0 * rg * (1.0 + (2.0 / 3.0 * (-direction.sign() * spin).acos()).cos()))
+    }
+
+    /// The magnitude of the dimensionless spin, if it describes a black hole
+    fn bounded_spin(&self) -> Option<f64> {
+        let spin = self.dimensionless_spin()?.abs();
+
+        if spin > 1.0 {
+            return None;
+        }
+        Some(spin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, SPEED_OF_LIGHT};
+
+    const MASS: f64 = 10.0 * SOLAR_MASS;
+
+    fn kerr_black_hole(spin: f64) -> BlackHole {
+        let rg = BlackHoleBuilder::new("Gargantua").mass(MASS).build().gravitational_radius().unwrap();
+
+        BlackHoleBuilder::new("Gargantua")
+            .mass(MASS)
+            .angular_momentum(spin * rg * MASS * SPEED_OF_LIGHT)
+            .build()
+    }
+
+    fn assert_radius(expected: f64, black_hole: &BlackHole, radius: Option<f64>) {
+        let rg = black_hole.gravitational_radius().unwrap();
+        assert!((radius.unwrap() / rg - expected).abs() < 1e-6, "{:?} != {}", radius.map(|r| r / rg), expected);
+    }
+
+    #[test]
+    fn schwarzschild_orbits() {
+
+        let black_hole = kerr_black_hole(0.0);
+
+        assert_radius(6.0, &black_hole, black_hole.isco_radius(OrbitDirection::Prograde));
+        assert_radius(6.0, &black_hole, black_hole.isco_radius(OrbitDirection::Retrograde));
+        assert_radius(3.0, &black_hole, black_hole.photon_orbit_radius(OrbitDirection::Prograde));
+        assert_radius(3.0, &black_hole, black_hole.photon_orbit_radius(OrbitDirection::Retrograde));
+    }
+
+    #[test]
+    fn extremal_kerr_orbits() {
+
+        // Bardeen, Press & Teukolsky (1972): for a = rg the prograde orbits approach the
+        // horizon at rg, the retrograde ISCO lies at 9 rg and the retrograde photon orbit at 4 rg
+        let black_hole = kerr_black_hole(1.0 - 1e-12);
+        let rg = black_hole.gravitational_radius().unwrap();
+
+        assert_radius(9.0, &black_hole, black_hole.isco_radius(OrbitDirection::Retrograde));
+        assert_radius(4.0, &black_hole, black_hole.photon_orbit_radius(OrbitDirection::Retrograde));
+        assert!((black_hole.isco_radius(OrbitDirection::Prograde).unwrap() / rg - 1.0).abs() < 1e-3);
+        assert!((black_hole.photon_orbit_radius(OrbitDirection::Prograde).unwrap() / rg - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn rapidly_rotating_kerr_orbits() {
+
+        // Thorne (1974) limit, the prograde ISCO is tabulated as 1.237 rg
+        let black_hole = kerr_black_hole(0.998);
+
+        assert_radius(1.236_970_7, &black_hole, black_hole.isco_radius(OrbitDirection::Prograde));
+        assert_radius(8.994_374_5, &black_hole, black_hole.isco_radius(OrbitDirection::Retrograde));
+        assert_radius(1.073_909_3, &black_hole, black_hole.photon_orbit_radius(OrbitDirection::Prograde));
+        assert_radius(3.998_221_9, &black_hole, black_hole.photon_orbit_radius(OrbitDirection::Retrograde));
+        assert_eq!(None, kerr_black_hole(1.5).isco_radius(OrbitDirection::Prograde));
+    }
+}
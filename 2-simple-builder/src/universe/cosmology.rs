@@ -0,0 +1,268 @@
+
+use std::f64::consts::PI;
+
+use universe::{BlackHole, SPEED_OF_LIGHT, simpson};
+
+const MEGAPARSEC: f64 = 3.085_677_581_491_367e22; // m
+const DISTANCE_INTEGRATION_INTERVALS: usize = 1024;
+
+// Planck 2018 results VI, A&A 641, A6
+const DEFAULT_HUBBLE_CONSTANT: f64 = 67.4; // km/s/Mpc
+const DEFAULT_MATTER_DENSITY: f64 = 0.315;
+
+/// A spatially flat Lambda-CDM universe, described by the Hubble constant (in km/s/Mpc)
+/// and the matter density parameter. Dark energy makes up the rest of the critical
+/// density, radiation is neglected. Distances are measured in meters, times in seconds.
+/// See: https://en.wikipedia.org/wiki/Distance_measure
+///
+/// # Examples
+///
+/// ```
+/// let cosmology = Cosmology::new(70.0, 0.3).unwrap();
+/// let distance = cosmology.luminosity_distance(2.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cosmology {
+    hubble_constant: f64,
+    matter_density: f64
+}
+
+impl Default for Cosmology {
+
+    /// The cosmology measured by the Planck satellite
+    fn default() -> Cosmology {
+        Cosmology {
+            hubble_constant:    DEFAULT_HUBBLE_CONSTANT,
+            matter_density:     DEFAULT_MATTER_DENSITY
+        }
+    }
+}
+
+impl Cosmology {
+
+    /// Constructs a flat universe with the given Hubble constant in km/s/Mpc and matter
+    /// density. Returns None unless the Hubble constant is positive and the matter density
+    /// lies in (0, 1], i.e. the dark energy density is not negative.
+    pub fn new(hubble_constant: f64, matter_density: f64) -> Option<Cosmology> {
+        if hubble_constant <= 0.0 || matter_density <= 0.0 || matter_density > 1.0 {
+            return None;
+        }
+        Some(Cosmology {
+            hubble_constant,
+            matter_density
+        })
+    }
+
+    /// The Hubble constant in km/s/Mpc
+    pub fn hubble_constant(&self) -> f64 {
+        self.hubble_constant
+    }
+
+    /// The density parameter of matter
+    pub fn matter_density(&self) -> f64 {
+        self.matter_density
+    }
+
+    /// The density parameter of dark energy
+    pub fn dark_energy_density(&self) -> f64 {
+        1.0 - self.matter_density
+    }
+
+    /// The Hubble time 1 / H0 in seconds
+    pub fn hubble_time(&self) -> f64 {
+        MEGAPARSEC / (self.hubble_constant * 1.0e3)
+    }
+
+    /// The Hubble distance c / H0 in meters
+    pub fn hubble_distance(&self) -> f64 {
+        SPEED_OF_LIGHT * self.hubble_time()
+    }
+
+    /// Calculates the expansion rate H(z) / H0
+    ///
+    /// E(z) = sqrt(Om * (1 + z)^3 + OL)
+    ///
+    pub fn expansion_rate(&self, redshift: f64) -> f64 {
+        (self.matter_density * (1.0 + redshift).powi(3) + self.dark_energy_density()).sqrt()
+    }
+
+    /// Calculates the comoving distance, which equals the transverse comoving distance
+    /// in a flat universe
+    ///
+    ///                  z
+    /// DC = c / H0 * integral dz' / E(z')
+    ///                  0
+    ///
+    pub fn comoving_distance(&self, redshift: f64) -> f64 {
+        self.hubble_distance() * simpson(|z| 1.0 / self.expansion_rate(z), 0.0, redshift,
+                                         DISTANCE_INTEGRATION_INTERVALS)
+    }
+
+    /// Calculates the luminosity distance, which relates the luminosity of a source to
+    /// its observed flux
+    ///
+    /// DL = (1 + z) * DC
+    ///
+    pub fn luminosity_distance(&self, redshift: f64) -> f64 {
+        (1.0 + redshift) * self.comoving_distance(redshift)
+    }
+
+    /// Calculates the angular diameter distance, which relates the size of a source to
+    /// the angle it subtends on the sky
+    ///
+    /// DA = DC / (1 + z)
+    ///
+    pub fn angular_diameter_distance(&self, redshift: f64) -> f64 {
+        self.comoving_distance(redshift) / (1.0 + redshift)
+    }
+
+    /// Calculates the age of the universe at the given redshift. Without dark energy
+    /// (Einstein-de Sitter universe) it reduces to t = 2 / (3 * H0) * (1 + z)^(-3/2).
+    ///
+    ///           2                  (      OL                   )
+    /// t = ----------------- * asinh( sqrt(--) * (1 + z)^(-3/2) )
+    ///     3 * H0 * sqrt(OL)        (      Om                   )
+    ///
+    pub fn age(&self, redshift: f64) -> f64 {
+        let dark_energy = self.dark_energy_density();
+        let scale = (1.0 + redshift).powf(-1.5);
+
+        if dark_energy <= 0.0 {
+            return 2.0 * self.hubble_time() / 3.0 * scale;
+        }
+        2.0 * self.hubble_time() / (3.0 * dark_energy.sqrt())
+            * ((dark_energy / self.matter_density).sqrt() * scale).asinh()
+    }
+
+    /// Calculates the time that has passed since the light we observe at the given
+    /// redshift was emitted
+    pub fn lookback_time(&self, redshift: f64) -> f64 {
+        self.age(0.0) - self.age(redshift)
+    }
+}
+
+/// The black hole in an expanding universe, seen from earth.
+///
+/// All methods return None if the redshift is unknown.
+impl BlackHole {
+
+    /// Calculates the mean angular diameter of the shadow in microarcseconds, as seen from
+    /// earth. The shadow is the image of the photon sphere, which (for a non-rotating black
+    /// hole) appears enlarged by gravitational lensing to a diameter of 2 * sqrt(27) * rg.
+    /// The full contour is given by `apparent_shadow_at_redshift`.
+    ///
+    ///     2 * sqrt(27) * rg
+    /// d ~ -----------------
+    ///            DA
+    ///
+    pub fn shadow_angular_size(&self, inclination: f64, cosmology: &Cosmology) -> Option<f64> {
+        Some(self.apparent_shadow_at_redshift(inclination, cosmology)?.diameter)
+    }
+
+    /// Calculates the time in seconds since the light we observe from the black hole
+    /// was emitted
+    pub fn lookback_time(&self, cosmology: &Cosmology) -> Option<f64> {
+        self.redshift.map(|redshift| cosmology.lookback_time(redshift))
+    }
+
+    /// Calculates the time in seconds the black hole had to grow from a seed which formed
+    /// at the given redshift until the moment we observe it. Returns None if the seed
+    /// formed later.
+    pub fn growth_time(&self, seed_redshift: f64, cosmology: &Cosmology) -> Option<f64> {
+        let time = cosmology.age(self.redshift?) - cosmology.age(seed_redshift);
+
+        if time < 0.0 {
+            return None;
+        }
+        Some(time)
+    }
+}
+
+/// Converts an angle in radians into microarcseconds
+pub fn microarcseconds(angle: f64) -> f64 {
+    angle * 180.0 / PI * 3600.0e6
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, Type};
+
+    const GIGAYEAR: f64 = 31_557_600.0e9;
+
+    fn assert_relative(expected: f64, actual: f64, tolerance: f64) {
+        assert!((actual / expected - 1.0).abs() < tolerance, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn planck_distances_and_times() {
+
+        let cosmology = Cosmology::default();
+
+        assert_relative(3401.26, cosmology.comoving_distance(1.0) / MEGAPARSEC, 1e-5);
+        assert_relative(6802.53, cosmology.luminosity_distance(1.0) / MEGAPARSEC, 1e-5);
+        assert_relative(1770.69, cosmology.angular_diameter_distance(2.0) / MEGAPARSEC, 1e-5);
+        assert_relative(13.796, cosmology.age(0.0) / GIGAYEAR, 1e-4);
+        assert_relative(7.9506, cosmology.lookback_time(1.0) / GIGAYEAR, 1e-4);
+
+        // the angular diameter distance has a maximum at z ~ 1.6
+        assert!(cosmology.angular_diameter_distance(1.6) > cosmology.angular_diameter_distance(1.0));
+        assert!(cosmology.angular_diameter_distance(1.6) > cosmology.angular_diameter_distance(2.5));
+    }
+
+    #[test]
+    fn einstein_de_sitter_limit() {
+
+        let cosmology = Cosmology::new(70.0, 1.0).unwrap();
+        let almost = Cosmology::new(70.0, 1.0 - 1e-9).unwrap();
+
+        assert_relative(2.0 / 3.0 * cosmology.hubble_time(), cosmology.age(0.0), 1e-12);
+        assert_relative(cosmology.age(0.0) / 8.0, cosmology.age(3.0), 1e-12);
+        assert_relative(cosmology.age(1.0), almost.age(1.0), 1e-8);
+        assert_eq!(0.0, cosmology.dark_energy_density());
+    }
+
+    #[test]
+    fn unphysical_parameters_are_rejected() {
+
+        assert_eq!(None, Cosmology::new(70.0, 1.2));
+        assert_eq!(None, Cosmology::new(70.0, 0.0));
+        assert_eq!(None, Cosmology::new(0.0, 0.3));
+        assert_eq!(Some(Cosmology::default()), Cosmology::new(67.4, 0.315));
+    }
+
+    #[test]
+    fn quasar_at_high_redshift() {
+
+        let cosmology = Cosmology::default();
+        let quasar = BlackHoleBuilder::new("J0313-1806")
+            .mass(1.6e9 * SOLAR_MASS)
+            .redshift(7.64)
+            .classification(Type::SuperMassive)
+            .build();
+
+        let growth = quasar.growth_time(30.0, &cosmology).unwrap() / GIGAYEAR;
+        assert!(growth > 0.5 && growth < 0.7, "{}", growth);
+        assert_eq!(None, quasar.growth_time(5.0, &cosmology));
+        assert!(quasar.lookback_time(&cosmology).unwrap() / GIGAYEAR > 13.0);
+        assert_eq!(None, BlackHoleBuilder::new("Nowhere").mass(1.0e30).build().lookback_time(&cosmology));
+    }
+
+    #[test]
+    fn shadow_of_m87() {
+
+        let m87 = BlackHoleBuilder::new("M87*")
+            .mass(6.5e9 * SOLAR_MASS)
+            .redshift(0.004_283)
+            .build();
+
+        let cosmology = Cosmology::default();
+        let size = m87.shadow_angular_size(17.0_f64.to_radians(), &cosmology).unwrap();
+        let expected = microarcseconds(2.0 * 27.0_f64.sqrt() * m87.gravitational_radius().unwrap()
+            / cosmology.angular_diameter_distance(0.004_283));
+
+        assert!((size / expected - 1.0).abs() < 1e-4, "{} {}", size, expected);
+        assert!(size > 30.0 && size < 40.0, "{}", size);
+    }
+}
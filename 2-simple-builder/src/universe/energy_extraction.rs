@@ -0,0 +1,121 @@
+
+use std::f64::consts::PI;
+
+use universe::{BlackHole, GRAVITATIONAL_CONSTANT, SPEED_OF_LIGHT};
+
+/// Energy which can be extracted from a rotating or charged black hole, e.g. by the
+/// Penrose process or the Blandford-Znajek process. Only the irreducible mass, which
+/// is tied to the area of the event horizon, can never be extracted.
+/// See: Christodoulou & Ruffini (1971), Phys. Rev. D 4, 3552
+///
+/// All methods return None if the mass is unknown or if the parameters describe
+/// a naked singularity.
+impl BlackHole {
+
+    /// Calculates the irreducible mass in kg
+    /// See: https://en.wikipedia.org/wiki/Irreducible_mass
+    ///
+    ///                 A * c^4
+    /// Mirr = sqrt(------------)
+    ///             16 * pi * G^2
+    ///
+    pub fn irreducible_mass(&self) -> Option<f64> {
+        Some((self.horizon_area()? * SPEED_OF_LIGHT.powi(4)
+            / (16.0 * PI * GRAVITATIONAL_CONSTANT.powi(2))).sqrt())
+    }
+
+    /// Calculates the total energy in Joules which can be extracted from the black hole,
+    /// consisting of its rotational and electromagnetic energy
+    ///
+    /// E = (M - Mirr) * c^2
+    ///
+    pub fn extractable_energy(&self) -> Option<f64> {
+        Some((self.mass? - self.irreducible_mass()?) * SPEED_OF_LIGHT.powi(2))
+    }
+
+    /// Calculates the rotational energy in Joules, i.e. the part of the extractable energy
+    /// which is lost when the black hole is spun down while its irreducible mass and
+    /// charge stay the same
+    ///
+    ///                    Q^2
+    /// E = (M - Mirr - ----------------------) * c^2
+    ///                 16 * pi * e0 * G * Mirr
+    ///
+    pub fn rotational_energy(&self) -> Option<f64> {
+        let irreducible_mass = self.irreducible_mass()?;
+        let irreducible_radius = GRAVITATIONAL_CONSTANT * irreducible_mass / SPEED_OF_LIGHT.powi(2);
+        let electromagnetic = self.charge_length_squared() / (4.0 * irreducible_radius)
+            * SPEED_OF_LIGHT.powi(2) / GRAVITATIONAL_CONSTANT;
+
+        Some((self.mass? - irreducible_mass - electromagnetic) * SPEED_OF_LIGHT.powi(2))
+    }
+
+    /// Calculates the fraction of the mass-energy which can be extracted at most, up to
+    /// 29% for a maximally rotating black hole
+    pub fn extractable_fraction(&self) -> Option<f64> {
+        Some(self.extractable_energy()? / (self.mass? * SPEED_OF_LIGHT.powi(2)))
+    }
+
+    /// Calculates the maximal efficiency of a single Penrose process, i.e. the energy gained
+    /// relative to the energy of the infalling particle, which splits up at the horizon.
+    /// It is 0 for a non-rotating and (sqrt(2) - 1) / 2 = 20.7% for a maximally rotating
+    /// black hole.
+    ///
+    ///       1        2 * rg
+    /// eta = - (sqrt(------) - 1)
+    ///       2         r+
+    ///
+    pub fn penrose_efficiency(&self) -> Option<f64> {
+        let rg = self.gravitational_radius()?;
+        let horizon = self.outer_horizon_radius()?;
+
+        Some(((2.0 * rg / horizon).sqrt() - 1.0) / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS};
+
+    fn black_hole(spin: f64, charge: f64) -> BlackHole {
+        let mass = 1.0e8 * SOLAR_MASS;
+        let rg = GRAVITATIONAL_CONSTANT * mass / SPEED_OF_LIGHT.powi(2);
+
+        BlackHoleBuilder::new("M87*")
+            .mass(mass)
+            .angular_momentum(spin * rg * mass * SPEED_OF_LIGHT)
+            .electric_charge(charge)
+            .build()
+    }
+
+    #[test]
+    fn schwarzschild_black_hole_has_no_extractable_energy() {
+
+        let black_hole = black_hole(0.0, 0.0);
+
+        assert!(black_hole.extractable_fraction().unwrap().abs() < 1e-12);
+        assert!(black_hole.penrose_efficiency().unwrap().abs() < 1e-12);
+    }
+
+    #[test]
+    fn nearly_extremal_kerr_black_hole() {
+
+        let black_hole = black_hole(1.0 - 1e-12, 0.0);
+
+        assert!((black_hole.extractable_fraction().unwrap() - (1.0 - 0.5_f64.sqrt())).abs() < 1e-5);
+        assert!((black_hole.penrose_efficiency().unwrap() - (2.0_f64.sqrt() - 1.0) / 2.0).abs() < 1e-5);
+        assert!((black_hole.rotational_energy().unwrap() / black_hole.extractable_energy().unwrap() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn charged_black_hole_stores_electromagnetic_energy() {
+
+        let uncharged = black_hole(0.5, 0.0);
+        let charged = black_hole(0.5, 1.0e28);
+
+        assert!(charged.extractable_energy() > uncharged.extractable_energy());
+        assert!(charged.rotational_energy().unwrap() < charged.extractable_energy().unwrap());
+    }
+}
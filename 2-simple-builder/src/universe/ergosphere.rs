@@ -0,0 +1,111 @@
+
+use std::f64::consts::PI;
+
+use universe::{BlackHole, simpson};
+
+const VOLUME_INTEGRATION_INTERVALS: usize = 256;
+
+/// The ergosphere is the region between the event horizon and the static limit.
+/// Inside of it nothing can stand still, everything is dragged along with the
+/// rotation of the black hole. A black hole without angular momentum is treated
+/// as non-rotating and therefore has an empty ergosphere.
+///
+/// All methods return None if the mass is unknown or if the parameters describe
+/// a naked singularity.
+impl BlackHole {
+
+    /// Calculates the radius of the static limit (the outer boundary of the ergosphere)
+    /// at the given polar angle theta (0 at the pole, pi/2 at the equator).
+    /// See: https://en.wikipedia.org/wiki/Ergosphere
+    ///
+    /// rE = rg + sqrt(rg^2 - a^2 * cos^2(theta) - rQ^2)
+    ///
+    pub fn static_limit_radius(&self, theta: f64) -> Option<f64> {
+        self.outer_horizon_radius()?;
+
+        let rg = self.gravitational_radius()?;
+        let a = self.spin_length()?;
+        let discriminant = rg.powi(2) - (a * theta.cos()).powi(2) - self.charge_length_squared();
+        Some(rg + discriminant.max(0.0).sqrt())
+    }
+
+    /// The extent of the ergosphere in the equatorial plane, where it is the largest
+    pub fn ergosphere_equatorial_radius(&self) -> Option<f64> {
+        self.static_limit_radius(PI / 2.0)
+    }
+
+    /// The extent of the ergosphere along the rotation axis, where it touches the
+    /// event horizon
+    pub fn ergosphere_polar_radius(&self) -> Option<f64> {
+        self.static_limit_radius(0.0)
+    }
+
+    /// Calculates the volume enclosed between the event horizon and the static limit.
+    ///
+    /// The Boyer-Lindquist coordinates are treated as oblate spheroidal coordinates
+    /// of flat space, which gives the volume element (r^2 + a^2 * cos^2(theta)) * sin(theta).
+    /// The radial part is integrated analytically, the polar part numerically.
+    pub fn ergosphere_volume(&self) -> Option<f64> {
+        let horizon = self.outer_horizon_radius()?;
+        let a = self.spin_length()?;
+
+        let radial = |r: f64, theta: f64| r.powi(3) / 3.0 + (a * theta.cos()).powi(2) * r;
+        let slice = |theta: f64| {
+            let static_limit = self.static_limit_radius(theta).unwrap_or(horizon);
+            (radial(static_limit, theta) - radial(horizon, theta)) * theta.sin()
+        };
+
+        Some(2.0 * PI * simpson(slice, 0.0, PI, VOLUME_INTEGRATION_INTERVALS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, SPEED_OF_LIGHT};
+
+    fn kerr_black_hole(spin: f64) -> BlackHole {
+        let builder = BlackHoleBuilder::new("Gargantua").mass(10.0 * SOLAR_MASS);
+        let rg = builder.build_copy().gravitational_radius().unwrap();
+
+        builder
+            .angular_momentum(spin * rg * 10.0 * SOLAR_MASS * SPEED_OF_LIGHT)
+            .build()
+    }
+
+    #[test]
+    fn non_rotating_black_hole_has_no_ergosphere() {
+
+        let black_hole = kerr_black_hole(0.0);
+        let horizon = black_hole.outer_horizon_radius().unwrap();
+
+        assert_eq!(Some(horizon), black_hole.ergosphere_equatorial_radius());
+        assert_eq!(Some(horizon), black_hole.ergosphere_polar_radius());
+        assert_eq!(Some(0.0), black_hole.ergosphere_volume());
+    }
+
+    #[test]
+    fn rapidly_rotating_black_hole_ergosphere() {
+
+        let black_hole = kerr_black_hole(0.998);
+        let rg = black_hole.gravitational_radius().unwrap();
+        let horizon = black_hole.outer_horizon_radius().unwrap();
+
+        assert!((black_hole.ergosphere_equatorial_radius().unwrap() - 2.0 * rg).abs() < 1e-9 * rg);
+        assert!((black_hole.ergosphere_polar_radius().unwrap() - horizon).abs() < 1e-9 * rg);
+        assert!(black_hole.ergosphere_volume().unwrap() > kerr_black_hole(0.5).ergosphere_volume().unwrap());
+    }
+
+    #[test]
+    fn extremal_ergosphere_volume() {
+
+        // for a = rg the static limit is rg * (1 + sin(theta)) and the horizon is at rg, so the
+        // volume integrates to (3 * pi^2 / 2 + 8 * pi / 3) * rg^3
+        let black_hole = kerr_black_hole(1.0 - 1e-12);
+        let rg = black_hole.gravitational_radius().unwrap();
+        let expected = (1.5 * PI.powi(2) + 8.0 * PI / 3.0) * rg.powi(3);
+
+        assert!((black_hole.ergosphere_volume().unwrap() / expected - 1.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,346 @@
+
+use universe::metric::Metric;
+
+// Butcher tableau of the Dormand-Prince 5(4) method (the geodesic equation does not
+// depend on the affine parameter, so the nodes are not needed)
+const COEFFICIENTS: [[f64; 6]; 7] = [
+    [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [1.0 / 5.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    [3.0 / 40.0, 9.0 / 40.0, 0.0, 0.0, 0.0, 0.0],
+    [44.0 / 45.0, -56.0 / 15.0, 32.0 / 9.0, 0.0, 0.0, 0.0],
+    [19372.0 / 6561.0, -25360.0 / 2187.0, 64448.0 / 6561.0, -212.0 / 729.0, 0.0, 0.0],
+    [9017.0 / 3168.0, -355.0 / 33.0, 46732.0 / 5247.0, 49.0 / 176.0, -5103.0 / 18656.0, 0.0],
+    [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0]
+];
+const WEIGHTS: [f64; 7] = [35.0 / 384.0, 0.0, 500.0 / 1113.0, 125.0 / 192.0, -2187.0 / 6784.0, 11.0 / 84.0, 0.0];
+const ERROR_WEIGHTS: [f64; 7] = [
+    35.0 / 384.0 - 5179.0 / 57600.0,
+    0.0,
+    500.0 / 1113.0 - 7571.0 / 16695.0,
+    125.0 / 192.0 - 393.0 / 640.0,
+    -2187.0 / 6784.0 + 92097.0 / 339200.0,
+    11.0 / 84.0 - 187.0 / 2100.0,
+    -1.0 / 40.0
+];
+
+const DEFAULT_TOLERANCE: f64 = 1e-10;
+const DEFAULT_INITIAL_STEP: f64 = 1e-2;
+const DEFAULT_MAX_STEPS: usize = 100_000;
+const DEFAULT_ESCAPE_RADIUS: f64 = 1000.0;
+const DEFAULT_HORIZON_MARGIN: f64 = 1e-2;
+const MIN_STEP: f64 = 1e-12;
+
+/// A point on a geodesic
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeodesicState {
+
+    /// The affine parameter (the proper time for timelike geodesics)
+    pub affine_parameter: f64,
+
+    /// The coordinates x^mu
+    pub position: [f64; 4],
+
+    /// The tangent vector u^mu = dx^mu / d(affine parameter)
+    pub velocity: [f64; 4]
+}
+
+/// The reason why the integration of a geodesic has stopped
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Termination {
+
+    /// The requested range of the affine parameter has been integrated
+    Completed,
+
+    /// The geodesic has reached the outer horizon
+    Horizon,
+
+    /// The geodesic has left the integration region
+    Escape,
+
+    /// The maximal number of steps has been reached or the step size has become too small
+    StepLimit
+}
+
+/// The largest deviation of the conserved quantities from their initial values along a
+/// trajectory. Energy and angular momentum deviations are relative (absolute if the
+/// initial value is smaller than 1), the deviation of the norm is absolute.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Drift {
+    pub energy: f64,
+    pub angular_momentum: f64,
+    pub norm: f64
+}
+
+/// An integrated geodesic
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trajectory {
+
+    /// All accepted integration steps, starting with the initial state
+    pub states: Vec<GeodesicState>,
+
+    /// Why the integration has stopped
+    pub termination: Termination,
+
+    /// How well the conserved quantities were preserved
+    pub drift: Drift
+}
+
+/// Integrates timelike and null geodesics of a Kerr-Newman metric with the adaptive
+/// Dormand-Prince 5(4) method. All lengths are measured in units of the gravitational
+/// radius (see Metric).
+///
+/// # Examples
+///
+/// ```
+/// let metric = black_hole.metric(Coordinates::BoyerLindquist).unwrap();
+/// let velocity = metric.complete_velocity(&position, &[-1.0, 0.0, 0.0], GeodesicKind::Null).unwrap();
+///
+/// let trajectory = GeodesicIntegrator::new()
+///     .tolerance(1e-8)
+///     .escape_radius(100.0)
+///     .integrate(&metric, position, velocity, 1000.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeodesicIntegrator {
+    tolerance: f64,
+    initial_step: f64,
+    max_steps: usize,
+    escape_radius: f64,
+    horizon_margin: f64
+}
+
+impl Default for GeodesicIntegrator {
+    fn default() -> GeodesicIntegrator {
+        GeodesicIntegrator {
+            tolerance:      DEFAULT_TOLERANCE,
+            initial_step:   DEFAULT_INITIAL_STEP,
+            max_steps:      DEFAULT_MAX_STEPS,
+            escape_radius:  DEFAULT_ESCAPE_RADIUS,
+            horizon_margin: DEFAULT_HORIZON_MARGIN
+        }
+    }
+}
+
+impl GeodesicIntegrator {
+
+    /// Constructs a new integrator with default settings
+    pub fn new() -> GeodesicIntegrator {
+        GeodesicIntegrator::default()
+    }
+
+    /// Sets the relative error tolerance per step
+    pub fn tolerance(mut self, tolerance: f64) -> GeodesicIntegrator {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Sets the size of the first step
+    pub fn initial_step(mut self, initial_step: f64) -> GeodesicIntegrator {
+        self.initial_step = initial_step;
+        self
+    }
+
+    /// Sets the maximal number of (accepted and rejected) steps
+    pub fn max_steps(mut self, max_steps: usize) -> GeodesicIntegrator {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Sets the radius beyond which a geodesic is considered to have escaped
+    pub fn escape_radius(mut self, escape_radius: f64) -> GeodesicIntegrator {
+        self.escape_radius = escape_radius;
+        self
+    }
+
+    /// Sets the relative distance to the outer horizon at which the integration stops.
+    /// Boyer-Lindquist coordinates are singular at the horizon, so it should not be too small.
+    pub fn horizon_margin(mut self, horizon_margin: f64) -> GeodesicIntegrator {
+        self.horizon_margin = horizon_margin;
+        self
+    }
+
+    /// Integrates the geodesic with the given initial position and velocity over the given
+    /// range of the affine parameter
+    pub fn integrate(&self, metric: &Metric, position: [f64; 4], velocity: [f64; 4], length: f64) -> Trajectory {
+        let mut states = vec![GeodesicState { affine_parameter: 0.0, position, velocity }];
+        let termination = self.trace(metric, position, velocity, length, |_, next| {
+            states.push(*next);
+            true
+        });
+
+        let initial = (metric.energy(&position, &velocity),
+                       metric.angular_momentum(&position, &velocity),
+                       metric.norm(&position, &velocity));
+        let deviation = |value: f64, initial: f64| (value - initial).abs() / initial.abs().max(1.0);
+        let drift = states.iter().fold(Drift { energy: 0.0, angular_momentum: 0.0, norm: 0.0 }, |drift, state| Drift {
+            energy:             drift.energy.max(deviation(metric.energy(&state.position, &state.velocity), initial.0)),
+            angular_momentum:   drift.angular_momentum.max(deviation(metric.angular_momentum(&state.position, &state.velocity), initial.1)),
+            norm:               drift.norm.max((metric.norm(&state.position, &state.velocity) - initial.2).abs())
+        });
+
+        Trajectory {
+            states,
+            termination,
+            drift
+        }
+    }
+
+    /// Integrates the geodesic without recording it. The visitor is called for every accepted
+    /// step with the previous and the new state and can stop the integration by returning
+    /// false, in which case Completed is returned.
+    pub fn trace<F>(&self, metric: &Metric, position: [f64; 4], velocity: [f64; 4], length: f64, mut visitor: F) -> Termination
+        where F: FnMut(&GeodesicState, &GeodesicState) -> bool {
+
+        let horizon = metric.horizon_radius() * (1.0 + self.horizon_margin);
+        let mut state = GeodesicState { affine_parameter: 0.0, position, velocity };
+        let mut step = self.initial_step;
+
+        for _ in 0..self.max_steps {
+            if state.affine_parameter >= length {
+                return Termination::Completed;
+            }
+            step = step.min(length - state.affine_parameter);
+
+            let (next, error) = self.dormand_prince_step(metric, &state, step);
+            if !error.is_finite() {
+                step /= 5.0;
+            } else if error <= 1.0 {
+                if !visitor(&state, &next) {
+                    return Termination::Completed;
+                }
+                state = next;
+
+                let radius = metric.radius(&state.position);
+                if radius <= horizon {
+                    return Termination::Horizon;
+                }
+                if radius >= self.escape_radius {
+                    return Termination::Escape;
+                }
+                step *= (0.9 * error.powf(-0.2)).min(5.0);
+            } else {
+                step *= (0.9 * error.powf(-0.2)).max(0.2);
+            }
+
+            if step < MIN_STEP {
+                return Termination::StepLimit;
+            }
+        }
+        Termination::StepLimit
+    }
+
+    /// Performs a single step and returns the new state together with the scaled error
+    fn dormand_prince_step(&self, metric: &Metric, state: &GeodesicState, step: f64) -> (GeodesicState, f64) {
+        let mut initial = [0.0; 8];
+        initial[..4].copy_from_slice(&state.position);
+        initial[4..].copy_from_slice(&state.velocity);
+
+        let mut stages = [[0.0; 8]; 7];
+        for stage in 0..7 {
+            let mut intermediate = initial;
+            for (previous, coefficient) in stages.iter().zip(COEFFICIENTS[stage].iter()).take(stage) {
+                for i in 0..8 {
+                    intermediate[i] += step * coefficient * previous[i];
+                }
+            }
+            stages[stage] = geodesic_equation(metric, &intermediate);
+        }
+
+        let mut result = initial;
+        let mut error = 0.0;
+        for i in 0..8 {
+            let mut difference = 0.0;
+            for stage in 0..7 {
+                result[i] += step * WEIGHTS[stage] * stages[stage][i];
+                difference += step * ERROR_WEIGHTS[stage] * stages[stage][i];
+            }
+            let scale = self.tolerance * (1.0 + initial[i].abs().max(result[i].abs()));
+            error += (difference / scale).powi(2);
+        }
+
+        let mut next = GeodesicState {
+            affine_parameter: state.affine_parameter + step,
+            position: [0.0; 4],
+            velocity: [0.0; 4]
+        };
+        next.position.copy_from_slice(&result[..4]);
+        next.velocity.copy_from_slice(&result[4..]);
+        (next, (error / 8.0).sqrt())
+    }
+}
+
+/// The right hand side of the geodesic equation for the state (x^mu, u^mu)
+///
+/// du^l / dlambda = -Gamma^l_mn * u^m * u^n
+///
+fn geodesic_equation(metric: &Metric, state: &[f64; 8]) -> [f64; 8] {
+    let mut position = [0.0; 4];
+    position.copy_from_slice(&state[..4]);
+    let symbols = metric.christoffel_symbols(&position);
+
+    let mut derivative = [0.0; 8];
+    derivative[..4].copy_from_slice(&state[4..]);
+    for l in 0..4 {
+        let mut acceleration = 0.0;
+        for m in 0..4 {
+            for n in 0..4 {
+                acceleration -= symbols[l][m][n] * state[4 + m] * state[4 + n];
+            }
+        }
+        derivative[4 + l] = acceleration;
+    }
+    derivative
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+    use universe::metric::{Coordinates, GeodesicKind};
+
+    #[test]
+    fn circular_orbit_stays_circular() {
+
+        let spin = 0.5;
+        let r: f64 = 10.0;
+        let metric = Metric::new(spin, 0.0, Coordinates::BoyerLindquist);
+
+        let angular_velocity = 1.0 / (r.powf(1.5) + spin);
+        let time = (r.powf(1.5) + spin) / (r.powf(0.75) * (r.powf(1.5) - 3.0 * r.sqrt() + 2.0 * spin).sqrt());
+        let velocity = [time, 0.0, 0.0, angular_velocity * time];
+        let period = 2.0 * PI / angular_velocity / time;
+
+        let trajectory = GeodesicIntegrator::new()
+            .integrate(&metric, [0.0, r, FRAC_PI_2, 0.0], velocity, 2.0 * period);
+        let last = trajectory.states.last().unwrap();
+
+        assert_eq!(Termination::Completed, trajectory.termination);
+        assert!((last.position[1] - r).abs() < 1e-6);
+        assert!((last.position[3] - 4.0 * PI).abs() < 1e-6);
+        assert!(trajectory.drift.energy < 1e-8);
+        assert!(trajectory.drift.angular_momentum < 1e-8);
+        assert!(trajectory.drift.norm < 1e-8);
+    }
+
+    #[test]
+    fn photons_are_captured_or_escape() {
+
+        let metric = Metric::new(0.9, 0.2, Coordinates::KerrSchild);
+        let integrator = GeodesicIntegrator::new().escape_radius(200.0);
+        let photon = |impact_parameter: f64| {
+            let position = [0.0, -100.0, impact_parameter, 0.0];
+            let velocity = metric.complete_velocity(&position, &[1.0, 0.0, 0.0], GeodesicKind::Null).unwrap();
+            integrator.integrate(&metric, position, velocity, 1000.0)
+        };
+
+        let captured = photon(2.0);
+        let escaped = photon(20.0);
+
+        assert_eq!(Termination::Horizon, captured.termination);
+        assert_eq!(Termination::Escape, escaped.termination);
+        assert!(escaped.drift.energy < 1e-8);
+        assert!(escaped.drift.norm < 1e-8);
+    }
+}
@@ -0,0 +1,231 @@
+
+use std::f64::consts::PI;
+
+use universe::{BlackHole, BlackHoleBuilder, Type, GRAVITATIONAL_CONSTANT, PROTON_MASS,
+               SPEED_OF_LIGHT, THOMSON_CROSS_SECTION};
+use universe::circular_orbit::OrbitDirection;
+
+/// The state of a growing black hole at a given time (in seconds since the start)
+#[derive(Debug, PartialEq)]
+pub struct Snapshot {
+    pub time: f64,
+    pub black_hole: BlackHole
+}
+
+/// The moment a growing black hole crosses the mass boundary between two types
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reclassification {
+    pub time: f64,
+    pub from: Type,
+    pub to: Type
+}
+
+/// The result of a growth simulation
+#[derive(Debug, PartialEq)]
+pub struct GrowthTimeline {
+    pub snapshots: Vec<Snapshot>,
+    pub reclassifications: Vec<Reclassification>
+}
+
+/// Simulates the growth of a black hole by Eddington-limited accretion over cosmic time.
+/// The black hole accretes at a fixed fraction of the Eddington rate while it is active
+/// (a fraction of the time given by the duty cycle). A share eta of the accreted rest mass
+/// is radiated away, the rest is added to the black hole, which therefore grows exponentially
+/// on the Salpeter timescale. The dimensionless spin and the electric charge are kept constant.
+/// See: https://en.wikipedia.org/wiki/Eddington_luminosity
+///
+/// # Examples
+///
+/// ```
+/// let timeline = GrowthSimulation::new()
+///     .duty_cycle(0.5)
+///     .efficiency(0.1)
+///     .run(&seed, 1.6e16, 1000)
+///     .unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GrowthSimulation {
+    eddington_ratio: f64,
+    duty_cycle: f64,
+    efficiency: Option<f64>
+}
+
+impl Default for GrowthSimulation {
+    fn default() -> GrowthSimulation {
+        GrowthSimulation::new()
+    }
+}
+
+impl GrowthSimulation {
+
+    /// Constructs a simulation of a black hole which permanently accretes at the Eddington
+    /// limit with the radiative efficiency of a thin disk
+    pub fn new() -> GrowthSimulation {
+        GrowthSimulation {
+            eddington_ratio:    1.0,
+            duty_cycle:         1.0,
+            efficiency:         None
+        }
+    }
+
+    /// Sets the luminosity during the active phases as a fraction of the Eddington luminosity
+    pub fn eddington_ratio(mut self, eddington_ratio: f64) -> GrowthSimulation {
+        self.eddington_ratio = eddington_ratio;
+        self
+    }
+
+    /// Sets the fraction of time during which the black hole is accreting
+    pub fn duty_cycle(mut self, duty_cycle: f64) -> GrowthSimulation {
+        self.duty_cycle = duty_cycle;
+        self
+    }
+
+    /// Sets the radiative efficiency (None uses the efficiency of a prograde thin disk
+    /// for the spin of the black hole)
+    pub fn efficiency<I>(mut self, efficiency: I) -> GrowthSimulation
+        where I: Into<Option<f64>> {
+
+        self.efficiency = efficiency.into();
+        self
+    }
+
+    /// Calculates the e-folding time of the mass in seconds (the Salpeter time). Returns None
+    /// if the black hole has no mass or no efficiency can be derived from its spin.
+    ///
+    ///         eta      sigmaT * c           1
+    /// tS = ------- * -------------- * ------------
+    ///      1 - eta   4 * pi * G * mp  lambda * fduty
+    ///
+    pub fn salpeter_time(&self, black_hole: &BlackHole) -> Option<f64> {
+        let efficiency = match self.efficiency {
+            Some(efficiency) => efficiency,
+            None => black_hole.radiative_efficiency(OrbitDirection::Prograde)?
+        };
+        let eddington_time = THOMSON_CROSS_SECTION * SPEED_OF_LIGHT
+            / (4.0 * PI * GRAVITATIONAL_CONSTANT * PROTON_MASS);
+
+        Some(efficiency / (1.0 - efficiency) * eddington_time
+            / (self.eddington_ratio * self.duty_cycle))
+    }
+
+    /// Evolves the black hole over the given duration (in seconds) and records a snapshot
+    /// after every one of the given number of equal steps (plus the initial state). The
+    /// snapshots are built from a builder which is updated at every step, its classification
+    /// is changed whenever the mass falls into the range of another type than the current
+    /// one (the stored classification or, if there is none, the one derived from the mass).
+    /// Returns None if the black hole has no mass or no efficiency can be derived.
+    pub fn run(&self, black_hole: &BlackHole, duration: f64, steps: usize) -> Option<GrowthTimeline> {
+        let initial_mass = black_hole.mass?;
+        let spin = black_hole.dimensionless_spin()?;
+        let salpeter_time = self.salpeter_time(black_hole)?;
+        let time_step = duration / steps.max(1) as f64;
+
+        let mut builder = BlackHoleBuilder::from(black_hole);
+        let mut classification = black_hole.classification.clone()
+            .unwrap_or_else(|| Type::from_mass(initial_mass));
+        let mut snapshots = vec![Snapshot { time: 0.0, black_hole: builder.build_copy() }];
+        let mut reclassifications = Vec::new();
+
+        for step in 1..=steps.max(1) {
+            let time = step as f64 * time_step;
+            let mass = initial_mass * (time / salpeter_time).exp();
+
+            builder = builder
+                .mass(mass)
+                .angular_momentum(black_hole.angular_momentum
+                    .map(|_| spin * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT));
+
+            let new_classification = Type::from_mass(mass);
+            if new_classification != classification {
+                reclassifications.push(Reclassification {
+                    time,
+                    from: classification,
+                    to: new_classification.clone()
+                });
+                builder = builder.classification(new_classification.clone());
+                classification = new_classification;
+            }
+            snapshots.push(Snapshot { time, black_hole: builder.build_copy() });
+        }
+        Some(GrowthTimeline { snapshots, reclassifications })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::SOLAR_MASS;
+
+    const JULIAN_YEAR: f64 = 31_557_600.0;
+
+    fn seed(spin: f64) -> BlackHole {
+        let mass = 150.0 * SOLAR_MASS;
+        BlackHoleBuilder::new("Seed")
+            .mass(mass)
+            .angular_momentum(spin * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT)
+            .classification(Type::IntermediateMassive)
+            .build()
+    }
+
+    #[test]
+    fn eddington_limited_growth_is_exponential() {
+
+        let simulation = GrowthSimulation::new();
+        let seed = seed(0.0);
+        let salpeter_time = simulation.salpeter_time(&seed).unwrap();
+        assert!((salpeter_time / JULIAN_YEAR / 1.0e6 - 27.4).abs() < 0.5);
+
+        let timeline = simulation.run(&seed, 5.0e8 * JULIAN_YEAR, 500).unwrap();
+        assert_eq!(501, timeline.snapshots.len());
+
+        let last = timeline.snapshots.last().unwrap();
+        let expected = 150.0 * SOLAR_MASS * (5.0e8 * JULIAN_YEAR / salpeter_time).exp();
+        assert!((last.black_hole.mass.unwrap() / expected - 1.0).abs() < 1e-12);
+        assert_eq!(Some(Type::SuperMassive), last.black_hole.classification);
+        assert_eq!("Seed", last.black_hole.name);
+
+        assert_eq!(1, timeline.reclassifications.len());
+        let reclassification = &timeline.reclassifications[0];
+        let crossing = salpeter_time * (1.0e5_f64 / 150.0).ln();
+        assert_eq!(Type::IntermediateMassive, reclassification.from);
+        assert_eq!(Type::SuperMassive, reclassification.to);
+        assert!(reclassification.time >= crossing && reclassification.time < crossing + JULIAN_YEAR * 1.0e6);
+    }
+
+    #[test]
+    fn stored_classification_is_corrected() {
+
+        let mass = 150.0 * SOLAR_MASS;
+        let seed = BlackHoleBuilder::new("Seed")
+            .mass(mass)
+            .angular_momentum(0.5 * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT)
+            .classification(Type::Stellar)
+            .build();
+        let timeline = GrowthSimulation::new().run(&seed, 1.0e6 * JULIAN_YEAR, 10).unwrap();
+
+        assert_eq!(Some(Type::Stellar), timeline.snapshots[0].black_hole.classification);
+        assert_eq!(1, timeline.reclassifications.len());
+        assert_eq!(Type::Stellar, timeline.reclassifications[0].from);
+        assert_eq!(Type::IntermediateMassive, timeline.reclassifications[0].to);
+        assert_eq!(timeline.snapshots[1].time, timeline.reclassifications[0].time);
+    }
+
+    #[test]
+    fn duty_cycle_and_efficiency_slow_down_growth() {
+
+        let seed = seed(0.9);
+        let eager = GrowthSimulation::new();
+        let lazy = GrowthSimulation::new().duty_cycle(0.5).efficiency(0.2);
+
+        let eager_time = eager.salpeter_time(&seed).unwrap();
+        let lazy_time = lazy.salpeter_time(&seed).unwrap();
+        let eta = seed.radiative_efficiency(OrbitDirection::Prograde).unwrap();
+        assert!((lazy_time / eager_time - 2.0 * (0.2 / 0.8) / (eta / (1.0 - eta))).abs() < 1e-12);
+
+        let timeline = lazy.run(&seed, 1.0e8 * JULIAN_YEAR, 10).unwrap();
+        let grown = &timeline.snapshots[10].black_hole;
+        assert!((grown.dimensionless_spin().unwrap() - 0.9).abs() < 1e-12);
+        assert!(timeline.reclassifications.is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+
+use std::f64::consts::PI;
+
+use universe::{BlackHole, GRAVITATIONAL_CONSTANT, SPEED_OF_LIGHT, VACUUM_PERMITTIVITY};
+
+/// Properties of the event horizon of a Kerr-Newman black hole. Together they
+/// enter the first law of black hole mechanics:
+///
+/// d(M * c^2) = kappa * c^2 / (8 * pi * G) * dA + OmegaH * dJ + PhiH * dQ
+///
+/// All methods return None if the mass is unknown or if the parameters describe
+/// a naked singularity.
+impl BlackHole {
+
+    /// Calculates the area of the event horizon in m^2
+    ///
+    /// A = 4 * pi * (r+^2 + a^2)
+    ///
+    pub fn horizon_area(&self) -> Option<f64> {
+        Some(4.0 * PI * self.horizon_radius_squared()?)
+    }
+
+    /// Calculates the surface gravity of the event horizon in m/s^2
+    ///
+    ///         c^2 * (r+ - r-)
+    /// kappa = ----------------
+    ///         2 * (r+^2 + a^2)
+    ///
+    pub fn surface_gravity(&self) -> Option<f64> {
+        let outer = self.outer_horizon_radius()?;
+        let inner = self.inner_horizon_radius()?;
+
+        Some(SPEED_OF_LIGHT.powi(2) * (outer - inner) / (2.0 * self.horizon_radius_squared()?))
+    }
+
+    /// Calculates the angular velocity of the event horizon in rad/s, i.e. how fast
+    /// the horizon is dragged around by the rotation of the black hole
+    ///
+    ///              a * c
+    /// OmegaH = -------------
+    ///          r+^2 + a^2
+    ///
+    pub fn horizon_angular_velocity(&self) -> Option<f64> {
+        Some(self.spin_length()? * SPEED_OF_LIGHT / self.horizon_radius_squared()?)
+    }
+
+    /// Calculates the electric potential of the event horizon in Volts
+    ///
+    ///                 Q * r+
+    /// PhiH = ---------------------------
+    ///        4 * pi * e0 * (r+^2 + a^2)
+    ///
+    pub fn horizon_electric_potential(&self) -> Option<f64> {
+        let charge = self.electric_charge.unwrap_or(0.0);
+
+        Some(charge * self.outer_horizon_radius()?
+            / (4.0 * PI * VACUUM_PERMITTIVITY * self.horizon_radius_squared()?))
+    }
+
+    /// Calculates the relative residual of the first law of black hole mechanics between
+    /// this black hole and a nearby one (e.g. created by `build_copy` from a slightly
+    /// modified builder). The coefficients are evaluated halfway between both black holes,
+    /// so the residual shrinks quadratically with the size of the perturbation.
+    pub fn first_law_residual(&self, other: &BlackHole) -> Option<f64> {
+        let mass_energy = (other.mass? - self.mass?) * SPEED_OF_LIGHT.powi(2);
+
+        let area_term = (self.surface_gravity()? + other.surface_gravity()?) / 2.0
+            * SPEED_OF_LIGHT.powi(2) / (8.0 * PI * GRAVITATIONAL_CONSTANT)
+            * (other.horizon_area()? - self.horizon_area()?);
+        let rotation_term = (self.horizon_angular_velocity()? + other.horizon_angular_velocity()?) / 2.0
+            * (other.angular_momentum.unwrap_or(0.0) - self.angular_momentum.unwrap_or(0.0));
+        let charge_term = (self.horizon_electric_potential()? + other.horizon_electric_potential()?) / 2.0
+            * (other.electric_charge.unwrap_or(0.0) - self.electric_charge.unwrap_or(0.0));
+
+        let scale = mass_energy.abs()
+            .max(area_term.abs())
+            .max(rotation_term.abs())
+            .max(charge_term.abs());
+
+        if scale == 0.0 {
+            return Some(0.0);
+        }
+        Some((mass_energy - area_term - rotation_term - charge_term).abs() / scale)
+    }
+
+    /// Checks whether the first law holds between this and a nearby black hole within
+    /// the given relative tolerance
+    pub fn satisfies_first_law(&self, other: &BlackHole, tolerance: f64) -> Option<bool> {
+        self.first_law_residual(other).map(|residual| residual <= tolerance)
+    }
+
+    /// r+^2 + a^2, which appears in all horizon properties
+    fn horizon_radius_squared(&self) -> Option<f64> {
+        Some(self.outer_horizon_radius()?.powi(2) + self.spin_length()?.powi(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::PI;
+
+    use universe::{BlackHoleBuilder, GRAVITATIONAL_CONSTANT, SOLAR_MASS, SPEED_OF_LIGHT};
+
+    const MASS: f64 = 10.0 * SOLAR_MASS;
+
+    #[test]
+    fn schwarzschild_horizon() {
+
+        let black_hole = BlackHoleBuilder::new("Cygnus X-1").mass(MASS).build();
+        let schwarzschild_radius = black_hole.outer_horizon_radius().unwrap();
+        let surface_gravity = SPEED_OF_LIGHT.powi(4) / (4.0 * GRAVITATIONAL_CONSTANT * MASS);
+
+        assert!((black_hole.horizon_area().unwrap() / schwarzschild_radius.powi(2) - 4.0 * PI).abs() < 1e-12);
+        assert!((black_hole.surface_gravity().unwrap() / surface_gravity - 1.0).abs() < 1e-12);
+        assert_eq!(Some(0.0), black_hole.horizon_angular_velocity());
+        assert_eq!(Some(0.0), black_hole.horizon_electric_potential());
+    }
+
+    #[test]
+    fn extremal_kerr_horizon() {
+
+        // for a = rg both horizons merge at rg: the area is half the Schwarzschild one,
+        // the surface gravity vanishes and the horizon rotates with c / (2 * rg)
+        let rg = GRAVITATIONAL_CONSTANT * MASS / SPEED_OF_LIGHT.powi(2);
+        let black_hole = BlackHoleBuilder::new("Gargantua")
+            .mass(MASS)
+            .angular_momentum((1.0 - 1e-12) * rg * MASS * SPEED_OF_LIGHT)
+            .build();
+
+        assert!((black_hole.horizon_area().unwrap() / (8.0 * PI * rg.powi(2)) - 1.0).abs() < 1e-5);
+        assert!(black_hole.surface_gravity().unwrap() < 1e-5 * SPEED_OF_LIGHT.powi(2) / (4.0 * rg));
+        assert!((black_hole.horizon_angular_velocity().unwrap() * 2.0 * rg / SPEED_OF_LIGHT - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn first_law_holds_for_nearby_black_holes() {
+
+        let builder = BlackHoleBuilder::new("Gargantua")
+            .mass(MASS)
+            .angular_momentum(4.0e43)
+            .electric_charge(5.0e20);
+
+        let black_hole = builder.build_copy();
+        let perturbed = builder
+            .mass(MASS * (1.0 + 1e-6))
+            .angular_momentum(4.0e43 * (1.0 - 2e-6))
+            .electric_charge(5.0e20 * (1.0 + 3e-6))
+            .build();
+
+        assert_eq!(Some(true), black_hole.satisfies_first_law(&perturbed, 1e-6));
+        assert_eq!(Some(false), black_hole.satisfies_first_law(
+            &BlackHoleBuilder::new("Gargantua").mass(MASS * (1.0 + 1e-6)).build(), 1e-6));
+    }
+}
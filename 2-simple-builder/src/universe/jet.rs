@@ -0,0 +1,96 @@
+
+use universe::{BlackHole, SPEED_OF_LIGHT, VACUUM_PERMITTIVITY};
+
+// Flux-geometry dependent factor of the Blandford-Znajek power, 1/(6*pi) for a split monopole
+// and 0.044 for a paraboloidal field, Tchekhovskoy, Narayan & McKinney (2010), ApJ 711, 50
+const BLANDFORD_ZNAJEK_FACTOR: f64 = 0.053;
+const SPIN_CORRECTION_QUADRATIC: f64 = 1.38;
+const SPIN_CORRECTION_QUARTIC: f64 = -9.2;
+
+/// Jets: magnetic field lines threading the horizon of a rotating black hole are twisted by
+/// the rotation of space-time, which extracts rotational energy as an electromagnetic outflow.
+///
+/// All methods return None if the mass is unknown or the black hole has no horizon.
+impl BlackHole {
+
+    /// Calculates the magnetic flux in Webers through one hemisphere of the event horizon
+    /// for a field of the given strength in Tesla
+    ///
+    ///        B * A
+    /// Phi = -------
+    ///          2
+    ///
+    pub fn magnetic_flux_from_field(&self, field_strength: f64) -> Option<f64> {
+        Some(field_strength * self.horizon_area()? / 2.0)
+    }
+
+    /// Calculates the power in Watts of a jet launched by the Blandford-Znajek process, using
+    /// the magnetic flux of the black hole. Returns None if the magnetic flux is unknown.
+    /// The spin dependence is accurate up to maximal spin.
+    /// See: https://en.wikipedia.org/wiki/Blandford%E2%80%93Znajek_process
+    ///
+    ///     kappa * Phi^2 * OmegaH^2                                  OmegaH * rg
+    /// P = ------------------------ * (1 + 1.38 * x^2 - 9.2 * x^4),  x = -----------
+    ///             mu0 * c                                                   c
+    ///
+    pub fn blandford_znajek_power(&self) -> Option<f64> {
+        let flux = self.magnetic_flux?;
+        let angular_velocity = self.horizon_angular_velocity()?;
+        let x = angular_velocity * self.gravitational_radius()? / SPEED_OF_LIGHT;
+        let vacuum_permeability = 1.0 / (VACUUM_PERMITTIVITY * SPEED_OF_LIGHT.powi(2));
+
+        Some(BLANDFORD_ZNAJEK_FACTOR * (flux * angular_velocity).powi(2)
+            / (vacuum_permeability * SPEED_OF_LIGHT)
+            * (1.0 + SPIN_CORRECTION_QUADRATIC * x.powi(2) + SPIN_CORRECTION_QUARTIC * x.powi(4)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::PI;
+
+    use super::*;
+    use universe::{BlackHoleBuilder, GRAVITATIONAL_CONSTANT, SOLAR_MASS, Type};
+
+    fn m87(spin: f64, flux: Option<f64>) -> BlackHole {
+        let mass = 6.5e9 * SOLAR_MASS;
+        BlackHoleBuilder::new("M87*")
+            .mass(mass)
+            .angular_momentum(spin * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT)
+            .magnetic_flux(flux)
+            .classification(Type::SuperMassive)
+            .build()
+    }
+
+    #[test]
+    fn jet_power_agrees_with_gaussian_units() {
+
+        let black_hole = m87(0.9, Some(1.0e25));
+        let power = black_hole.blandford_znajek_power().unwrap();
+
+        // P = kappa / (4 * pi * c) * Phi^2 * OmegaH^2 * f in erg/s, with 1 Wb = 10^8 Mx
+        let omega = black_hole.horizon_angular_velocity().unwrap();
+        let x = omega * black_hole.gravitational_radius().unwrap() / SPEED_OF_LIGHT;
+        let gaussian = 0.053 / (4.0 * PI * SPEED_OF_LIGHT * 100.0) * (1.0e25 * 1.0e8 * omega).powi(2)
+            * (1.0 + 1.38 * x.powi(2) - 9.2 * x.powi(4));
+
+        assert!((power / (gaussian * 1.0e-7) - 1.0).abs() < 1e-6);
+        assert!(power > 1.0e35 && power < 1.0e38);
+    }
+
+    #[test]
+    fn jet_power_grows_with_spin() {
+
+        let slow = m87(0.05, Some(1.0e25)).blandford_znajek_power().unwrap();
+        let fast = m87(0.1, Some(1.0e25)).blandford_znajek_power().unwrap();
+
+        assert!((fast / slow - 4.0).abs() < 0.05);
+        assert_eq!(Some(0.0), m87(0.0, Some(1.0e25)).blandford_znajek_power());
+        assert_eq!(None, m87(0.9, None).blandford_znajek_power());
+
+        let black_hole = m87(0.9, None);
+        let flux = black_hole.magnetic_flux_from_field(1.0).unwrap();
+        assert!((flux - black_hole.horizon_area().unwrap() / 2.0).abs() < 1e-6 * flux);
+    }
+}
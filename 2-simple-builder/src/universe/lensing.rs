@@ -0,0 +1,204 @@
+
+use std::f64::consts::PI;
+
+use universe::BlackHole;
+
+// Coefficients of the strong deflection limit of a Schwarzschild black hole
+// See: Bozza (2002), Phys. Rev. D 66, 103001
+const STRONG_DEFLECTION_A: f64 = 1.0;
+const STRONG_DEFLECTION_B: f64 = -0.4002;
+
+/// One of the two images of a lensed point source
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LensedImage {
+
+    /// The angular position relative to the lens in radians, with the same sign
+    /// convention as the position of the source
+    pub position: f64,
+
+    /// The magnification (negative for images with inverted parity)
+    pub magnification: f64
+}
+
+/// A black hole acting as a gravitational lens for a source behind it. All angles
+/// are measured in radians as seen by the observer, all distances in meters. The
+/// distance between the observer and the source is the sum of both distances, so
+/// cosmological distances have to be converted beforehand.
+///
+/// The weak-field methods treat the black hole as a point mass, the strong deflection
+/// limit near the photon sphere neglects rotation and charge.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GravitationalLens {
+    gravitational_radius: f64,
+    observer_distance: f64,
+    source_distance: f64
+}
+
+impl GravitationalLens {
+
+    /// Places the black hole between an observer and a source. Returns None if the
+    /// mass of the black hole is unknown.
+    pub fn new(black_hole: &BlackHole, observer_distance: f64, source_distance: f64) -> Option<GravitationalLens> {
+        Some(GravitationalLens {
+            gravitational_radius:   black_hole.gravitational_radius()?,
+            observer_distance,
+            source_distance
+        })
+    }
+
+    /// Calculates the Einstein radius, the radius of the ring seen if observer, lens and
+    /// source are perfectly aligned.
+    /// See: https://en.wikipedia.org/wiki/Einstein_radius
+    ///
+    ///               4 * G * M      Dls
+    /// thetaE = sqrt(--------- * ---------)
+    ///                 c^2       Dol * Ds
+    ///
+    pub fn einstein_radius(&self) -> f64 {
+        let total_distance = self.observer_distance + self.source_distance;
+
+        (4.0 * self.gravitational_radius * self.source_distance
+            / (self.observer_distance * total_distance)).sqrt()
+    }
+
+    /// Calculates the weak-field deflection angle of a light ray passing the black hole
+    /// with the given impact parameter
+    ///
+    ///         4 * G * M
+    /// alpha = ---------
+    ///          c^2 * b
+    ///
+    pub fn deflection_angle(&self, impact_parameter: f64) -> f64 {
+        4.0 * self.gravitational_radius / impact_parameter
+    }
+
+    /// Calculates both images of a point source at the given angular distance from the lens.
+    /// The first image lies on the same side as the source, the second one on the opposite
+    /// side. Returns None for a perfectly aligned source (beta = 0), which is imaged into the
+    /// Einstein ring with a diverging magnification instead.
+    ///
+    /// theta = (beta +/- sqrt(beta^2 + 4 * thetaE^2)) / 2
+    ///
+    ///          u^2 + 2        1
+    /// mu = --------------- +/- -    with u = |beta| / thetaE
+    ///      2u * sqrt(u^2 + 4)  2
+    ///
+    pub fn images(&self, source_position: f64) -> Option<[LensedImage; 2]> {
+        if source_position == 0.0 {
+            return None;
+        }
+
+        let einstein_radius = self.einstein_radius();
+        let beta = source_position.abs();
+        let root = (beta.powi(2) + 4.0 * einstein_radius.powi(2)).sqrt();
+        let u = beta / einstein_radius;
+        let magnification = (u.powi(2) + 2.0) / (2.0 * u * (u.powi(2) + 4.0).sqrt());
+        let side = source_position.signum();
+
+        Some([
+            LensedImage {
+                position:       side * (beta + root) / 2.0,
+                magnification:  magnification + 0.5
+            },
+            LensedImage {
+                position:       side * (beta - root) / 2.0,
+                magnification:  -(magnification - 0.5)
+            }
+        ])
+    }
+
+    /// The critical impact parameter (in meters) below which light is captured by the
+    /// black hole
+    ///
+    /// bc = 3 * sqrt(3) * G * M / c^2
+    ///
+    pub fn critical_impact_parameter(&self) -> f64 {
+        3.0 * 3.0_f64.sqrt() * self.gravitational_radius
+    }
+
+    /// Calculates the deflection angle of a light ray passing close to the photon sphere,
+    /// where it may wind around the black hole several times. Returns None if the ray is
+    /// captured.
+    ///
+    /// alpha = -a * ln(b / bc - 1) + b
+    ///
+    pub fn strong_deflection_angle(&self, impact_parameter: f64) -> Option<f64> {
+        let critical = self.critical_impact_parameter();
+
+        if impact_parameter <= critical {
+            return None;
+        }
+        Some(-STRONG_DEFLECTION_A * (impact_parameter / critical - 1.0).ln() + STRONG_DEFLECTION_B)
+    }
+
+    /// Calculates the angular radius of the n-th relativistic Einstein ring (n >= 1), formed
+    /// by light which winds n times around the black hole. With increasing n, the rings
+    /// approach the shadow of the black hole exponentially fast.
+    ///
+    /// thetaN = thetaInf * (1 + exp((b - 2 * pi * n) / a))
+    ///
+    pub fn relativistic_ring_radius(&self, n: u32) -> f64 {
+        let shadow = self.critical_impact_parameter() / self.observer_distance;
+
+        shadow * (1.0 + ((STRONG_DEFLECTION_B - 2.0 * PI * f64::from(n)) / STRONG_DEFLECTION_A).exp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS};
+
+    const KILOPARSEC: f64 = 3.085_677_58e19;
+    const MILLIARCSECONDS_PER_RADIAN: f64 = 206_264_806.0;
+
+    fn microlens() -> GravitationalLens {
+        let black_hole = BlackHoleBuilder::new("OGLE-2011-BLG-0462").mass(SOLAR_MASS).build();
+        GravitationalLens::new(&black_hole, 4.0 * KILOPARSEC, 4.0 * KILOPARSEC).unwrap()
+    }
+
+    #[test]
+    fn weak_field_lensing() {
+
+        let lens = microlens();
+        let einstein_radius = lens.einstein_radius();
+        let images = lens.images(einstein_radius).unwrap();
+
+        assert!((einstein_radius * MILLIARCSECONDS_PER_RADIAN - 1.01).abs() < 0.01);
+        assert!((lens.deflection_angle(6.957e8) * MILLIARCSECONDS_PER_RADIAN / 1000.0 - 1.75).abs() < 0.01);
+        assert!((images[0].position * images[1].position + einstein_radius.powi(2)).abs() < 1e-12 * einstein_radius.powi(2));
+        assert!((images[0].magnification - images[1].magnification - 1.342).abs() < 1e-3);
+        assert!((images[0].magnification + images[1].magnification - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn images_mirror_with_the_source() {
+
+        let lens = microlens();
+        let einstein_radius = lens.einstein_radius();
+        let left = lens.images(-0.5 * einstein_radius).unwrap();
+        let right = lens.images(0.5 * einstein_radius).unwrap();
+
+        assert_eq!(-right[0].position, left[0].position);
+        assert_eq!(-right[1].position, left[1].position);
+        assert_eq!(right[0].magnification, left[0].magnification);
+        assert_eq!(right[1].magnification, left[1].magnification);
+        assert!(left[0].position < 0.0 && left[0].magnification > 1.0);
+        assert_eq!(None, lens.images(0.0));
+    }
+
+    #[test]
+    fn strong_deflection_limit() {
+
+        let lens = microlens();
+        let critical = lens.critical_impact_parameter();
+        let shadow = critical / (4.0 * KILOPARSEC);
+
+        assert_eq!(None, lens.strong_deflection_angle(critical));
+        assert!(lens.strong_deflection_angle(1.001 * critical).unwrap() > 2.0 * PI);
+        assert!(lens.relativistic_ring_radius(1) > lens.relativistic_ring_radius(2));
+        assert!((lens.relativistic_ring_radius(1) / shadow - 1.0).abs() < 2e-3);
+        assert!(lens.relativistic_ring_radius(1) < lens.einstein_radius());
+    }
+}
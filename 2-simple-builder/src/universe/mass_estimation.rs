@@ -0,0 +1,241 @@
+
+use universe::{GRAVITATIONAL_CONSTANT, SOLAR_MASS};
+
+// M-sigma and M-bulge relations of Kormendy & Ho (2013), ARA&A 51, 511
+const M_SIGMA_INTERCEPT: f64 = 8.49;
+const M_SIGMA_SLOPE: f64 = 4.38;
+const M_SIGMA_SCATTER: f64 = 0.29; // dex
+const M_SIGMA_REFERENCE_DISPERSION: f64 = 200_000.0; // m/s
+
+const M_BULGE_NORMALIZATION: f64 = 0.49e9; // solar masses
+const M_BULGE_SLOPE: f64 = 1.16;
+const M_BULGE_SCATTER: f64 = 0.28; // dex
+const M_BULGE_REFERENCE_MASS: f64 = 1.0e11; // solar masses
+
+// Virial factor for FWHM line widths of Woo et al. (2015), ApJ 801, 38, the scatter is
+// dominated by the object-to-object variation of the geometry of the broad line region
+const DEFAULT_VIRIAL_FACTOR: f64 = 1.12;
+const DEFAULT_VIRIAL_SCATTER: f64 = 0.4; // dex
+
+/// The method a mass estimate is based on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EstimationMethod {
+    MSigma,
+    MBulge,
+    Virial,
+    Dynamical
+}
+
+/// A mass in kg, derived from an indirect estimate. The scatter (in dex) is the intrinsic
+/// scatter of the relation the estimate is based on, or the statistical uncertainty of a
+/// dynamical measurement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MassEstimate {
+    pub mass: f64,
+    pub scatter: f64,
+    pub method: EstimationMethod
+}
+
+impl MassEstimate {
+
+    /// Calculates the one sigma range of the mass
+    pub fn range(&self) -> (f64, f64) {
+        let factor = 10.0_f64.powf(self.scatter);
+        (self.mass / factor, self.mass * factor)
+    }
+}
+
+/// Estimates the mass of a black hole which cannot be measured directly (e.g. from
+/// stellar orbits) from properties of its host galaxy or its accretion flow
+///
+/// # Examples
+///
+/// ```
+/// let black_hole = BlackHoleBuilder::new("NGC 4395")
+///     .mass_from(&VelocityDispersion::new(30_000.0))
+///     .build();
+/// ```
+pub trait MassEstimator {
+
+    /// Estimates the mass of the black hole
+    fn estimate(&self) -> MassEstimate;
+}
+
+/// The M-sigma relation between the mass of a black hole and the stellar velocity
+/// dispersion (in m/s) of the bulge of its host galaxy.
+/// See: https://en.wikipedia.org/wiki/M%E2%80%93sigma_relation
+///
+///     (  M   )                     (  sigma   )
+/// log (------) = 8.49 + 4.38 * log (----------)
+///     ( Msun )                     ( 200 km/s )
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VelocityDispersion {
+    pub dispersion: f64
+}
+
+impl VelocityDispersion {
+
+    /// Constructs the estimator for the given velocity dispersion in m/s
+    pub fn new(dispersion: f64) -> VelocityDispersion {
+        VelocityDispersion {
+            dispersion
+        }
+    }
+}
+
+impl MassEstimator for VelocityDispersion {
+    fn estimate(&self) -> MassEstimate {
+        let log_mass = M_SIGMA_INTERCEPT
+            + M_SIGMA_SLOPE * (self.dispersion / M_SIGMA_REFERENCE_DISPERSION).log10();
+
+        MassEstimate {
+            mass:       10.0_f64.powf(log_mass) * SOLAR_MASS,
+            scatter:    M_SIGMA_SCATTER,
+            method:     EstimationMethod::MSigma
+        }
+    }
+}
+
+/// The relation between the mass of a black hole and the stellar mass (in kg) of the
+/// bulge of its host galaxy
+///
+///     M              (  Mbulge  )^1.16
+/// --------- = 0.49 * (----------)
+/// 10^9 Msun          (10^11 Msun)
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BulgeMass {
+    pub mass: f64
+}
+
+impl BulgeMass {
+
+    /// Constructs the estimator for the given bulge mass in kg
+    pub fn new(mass: f64) -> BulgeMass {
+        BulgeMass {
+            mass
+        }
+    }
+}
+
+impl MassEstimator for BulgeMass {
+    fn estimate(&self) -> MassEstimate {
+        let bulge = self.mass / SOLAR_MASS / M_BULGE_REFERENCE_MASS;
+
+        MassEstimate {
+            mass:       M_BULGE_NORMALIZATION * bulge.powf(M_BULGE_SLOPE) * SOLAR_MASS,
+            scatter:    M_BULGE_SCATTER,
+            method:     EstimationMethod::MBulge
+        }
+    }
+}
+
+/// The virial mass of the broad line region of an active galactic nucleus, from the
+/// width (FWHM in m/s) of a broad emission line and the radius (in m) of the emitting
+/// region. The radius is either measured by reverberation mapping (the light travel
+/// time of the lag between continuum and line variations) or, for single-epoch spectra,
+/// inferred from the radius-luminosity relation.
+/// See: https://en.wikipedia.org/wiki/Reverberation_mapping
+///
+///     f * R * dV^2
+/// M = ------------
+///          G
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Virial {
+    pub line_width: f64,
+    pub radius: f64,
+    pub virial_factor: f64,
+    pub scatter: f64
+}
+
+impl Virial {
+
+    /// Constructs the estimator for the given line width in m/s and radius in m
+    pub fn new(line_width: f64, radius: f64) -> Virial {
+        Virial {
+            line_width,
+            radius,
+            virial_factor:  DEFAULT_VIRIAL_FACTOR,
+            scatter:        DEFAULT_VIRIAL_SCATTER
+        }
+    }
+
+    /// Sets the virial factor, which accounts for the unknown geometry and inclination
+    /// of the broad line region
+    pub fn virial_factor(mut self, virial_factor: f64) -> Virial {
+        self.virial_factor = virial_factor;
+        self
+    }
+
+    /// Sets the scatter in dex
+    pub fn scatter(mut self, scatter: f64) -> Virial {
+        self.scatter = scatter;
+        self
+    }
+}
+
+impl MassEstimator for Virial {
+    fn estimate(&self) -> MassEstimate {
+        MassEstimate {
+            mass:       self.virial_factor * self.radius * self.line_width.powi(2) / GRAVITATIONAL_CONSTANT,
+            scatter:    self.scatter,
+            method:     EstimationMethod::Virial
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SPEED_OF_LIGHT};
+
+    fn solar_masses(estimate: &MassEstimate) -> f64 {
+        estimate.mass / SOLAR_MASS
+    }
+
+    #[test]
+    fn scaling_relations() {
+
+        let m_sigma = VelocityDispersion::new(200_000.0).estimate();
+        assert!((solar_masses(&m_sigma) / 10.0_f64.powf(8.49) - 1.0).abs() < 1e-12);
+        assert_eq!(0.29, m_sigma.scatter);
+        assert_eq!(EstimationMethod::MSigma, m_sigma.method);
+
+        let heavier = VelocityDispersion::new(400_000.0).estimate();
+        assert!((heavier.mass / m_sigma.mass - 2.0_f64.powf(4.38)).abs() < 1e-9);
+
+        let m_bulge = BulgeMass::new(1.0e11 * SOLAR_MASS).estimate();
+        assert!((solar_masses(&m_bulge) / 0.49e9 - 1.0).abs() < 1e-12);
+        assert_eq!(EstimationMethod::MBulge, m_bulge.method);
+
+        let (lower, upper) = m_bulge.range();
+        assert!((upper / lower - 10.0_f64.powf(0.56)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reverberation_mapping_of_ngc_5548() {
+
+        // H-beta lag of about 4 light days and a line width of 4500 km/s
+        let radius = 4.0 * 86_400.0 * SPEED_OF_LIGHT;
+        let estimate = Virial::new(4.5e6, radius).estimate();
+
+        assert!(solar_masses(&estimate) > 1.0e7 && solar_masses(&estimate) < 2.0e7);
+        assert_eq!(EstimationMethod::Virial, estimate.method);
+    }
+
+    #[test]
+    fn builder_fills_only_unknown_masses() {
+
+        let estimator = VelocityDispersion::new(75_000.0);
+        let estimated = BlackHoleBuilder::new("Estimated").mass_from(&estimator).build();
+        let measured = BlackHoleBuilder::new("Measured").mass(8.26e36).mass_from(&estimator).build();
+
+        assert_eq!(Some(estimator.estimate().mass), estimated.mass);
+        assert_eq!(Some(estimator.estimate()), estimated.mass_estimate);
+        assert_eq!(Some(8.26e36), measured.mass);
+        assert_eq!(None, measured.mass_estimate);
+    }
+}
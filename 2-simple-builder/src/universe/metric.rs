@@ -0,0 +1,323 @@
+
+use universe::{BlackHole, invert};
+
+/// Relative step used for the numerical derivatives of the metric
+const DERIVATIVE_STEP: f64 = 1e-5;
+
+/// A 4x4 tensor with two indices, e.g. the metric g_mu_nu
+pub type Tensor = [[f64; 4]; 4];
+
+/// The Christoffel symbols Gamma^lambda_mu_nu, indexed as [lambda][mu][nu]
+pub type Christoffel = [[[f64; 4]; 4]; 4];
+
+/// The coordinate systems in which the metric can be evaluated
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Coordinates {
+
+    /// (t, r, theta, phi), singular at the horizons and at the poles
+    BoyerLindquist,
+
+    /// Cartesian (t, x, y, z) in the ingoing Kerr-Schild form, regular at the horizons
+    KerrSchild
+}
+
+/// The kinds of geodesics
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GeodesicKind {
+
+    /// Followed by massive particles, parametrized by proper time
+    Timelike,
+
+    /// Followed by light
+    Null
+}
+
+/// The Kerr-Newman metric, which describes the spacetime around a rotating and
+/// charged black hole.
+/// See: https://en.wikipedia.org/wiki/Kerr%E2%80%93Newman_metric
+///
+/// The metric uses geometrized units: all lengths (including the time coordinate)
+/// are measured in units of the gravitational radius GM/c^2, so the black hole has
+/// a mass of 1. The signature is (-, +, +, +).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Metric {
+
+    /// The dimensionless spin a / rg
+    pub spin: f64,
+
+    /// The dimensionless charge rQ / rg
+    pub charge: f64,
+
+    /// The coordinate system
+    pub coordinates: Coordinates
+}
+
+impl Metric {
+
+    /// Constructs the metric of a black hole with the given dimensionless spin and charge
+    pub fn new(spin: f64, charge: f64, coordinates: Coordinates) -> Metric {
+        Metric {
+            spin,
+            charge,
+            coordinates
+        }
+    }
+
+    /// The radius of the outer horizon
+    pub fn horizon_radius(&self) -> f64 {
+        1.0 + (1.0 - self.spin.powi(2) - self.charge.powi(2)).max(0.0).sqrt()
+    }
+
+    /// Calculates the (Boyer-Lindquist) radius r of the given position. In Kerr-Schild
+    /// coordinates it is the solution of r^4 - (x^2 + y^2 + z^2 - a^2) * r^2 - a^2 * z^2 = 0.
+    pub fn radius(&self, position: &[f64; 4]) -> f64 {
+        match self.coordinates {
+            Coordinates::BoyerLindquist => position[1],
+            Coordinates::KerrSchild => {
+                let a2 = self.spin.powi(2);
+                let b = position[1].powi(2) + position[2].powi(2) + position[3].powi(2) - a2;
+                ((b + (b.powi(2) + 4.0 * a2 * position[3].powi(2)).sqrt()) / 2.0).sqrt()
+            }
+        }
+    }
+
+    /// Evaluates the covariant metric components g_mu_nu at the given position
+    pub fn components(&self, position: &[f64; 4]) -> Tensor {
+        match self.coordinates {
+            Coordinates::BoyerLindquist => self.boyer_lindquist(position),
+            Coordinates::KerrSchild => self.kerr_schild(position)
+        }
+    }
+
+    /// Evaluates the contravariant metric components g^mu^nu at the given position. The
+    /// components are NaN where the metric is degenerate (e.g. on the rotation axis in
+    /// Boyer-Lindquist coordinates).
+    pub fn inverse_components(&self, position: &[f64; 4]) -> Tensor {
+        invert(&self.components(position)).unwrap_or([[f64::NAN; 4]; 4])
+    }
+
+    /// Evaluates the Christoffel symbols of the second kind at the given position. The
+    /// derivatives of the metric are calculated numerically with central differences;
+    /// the metric does not depend on the time coordinate.
+    ///
+    /// Gamma^l_mn = 1/2 * g^ls * (d_m g_sn + d_n g_sm - d_s g_mn)
+    ///
+    pub fn christoffel_symbols(&self, position: &[f64; 4]) -> Christoffel {
+        let inverse = self.inverse_components(position);
+        let mut derivatives = [[[0.0; 4]; 4]; 4];
+
+        for (k, derivative) in derivatives.iter_mut().enumerate().skip(1) {
+            if k == 3 && self.coordinates == Coordinates::BoyerLindquist {
+                continue;
+            }
+
+            let step = DERIVATIVE_STEP * position[k].abs().max(1.0);
+            let mut forward = *position;
+            let mut backward = *position;
+            forward[k] += step;
+            backward[k] -= step;
+
+            let upper = self.components(&forward);
+            let lower = self.components(&backward);
+            for i in 0..4 {
+                for j in 0..4 {
+                    derivative[i][j] = (upper[i][j] - lower[i][j]) / (2.0 * step);
+                }
+            }
+        }
+
+        let mut symbols = [[[0.0; 4]; 4]; 4];
+        for (l, symbol) in symbols.iter_mut().enumerate() {
+            for m in 0..4 {
+                for n in m..4 {
+                    let value: f64 = (0..4)
+                        .map(|s| inverse[l][s]
+                            * (derivatives[m][s][n] + derivatives[n][s][m] - derivatives[s][m][n]))
+                        .sum::<f64>() / 2.0;
+                    symbol[m][n] = value;
+                    symbol[n][m] = value;
+                }
+            }
+        }
+        symbols
+    }
+
+    /// Calculates g_mu_nu * u^mu * u^nu, which is -1 for timelike and 0 for null
+    /// geodesics parametrized by proper time or an affine parameter
+    pub fn norm(&self, position: &[f64; 4], velocity: &[f64; 4]) -> f64 {
+        let metric = self.components(position);
+        (0..4).map(|i| (0..4).map(|j| metric[i][j] * velocity[i] * velocity[j]).sum::<f64>()).sum()
+    }
+
+    /// Lowers the index of the given vector
+    pub fn lower(&self, position: &[f64; 4], velocity: &[f64; 4]) -> [f64; 4] {
+        let metric = self.components(position);
+        let mut covariant = [0.0; 4];
+        for (i, component) in covariant.iter_mut().enumerate() {
+            *component = (0..4).map(|j| metric[i][j] * velocity[j]).sum();
+        }
+        covariant
+    }
+
+    /// The conserved energy per unit mass -u_t, which belongs to the time translation symmetry
+    pub fn energy(&self, position: &[f64; 4], velocity: &[f64; 4]) -> f64 {
+        -self.lower(position, velocity)[0]
+    }
+
+    /// The conserved angular momentum per unit mass around the rotation axis, which belongs
+    /// to the rotational symmetry
+    pub fn angular_momentum(&self, position: &[f64; 4], velocity: &[f64; 4]) -> f64 {
+        let covariant = self.lower(position, velocity);
+        match self.coordinates {
+            Coordinates::BoyerLindquist => covariant[3],
+            Coordinates::KerrSchild => position[1] * covariant[2] - position[2] * covariant[1]
+        }
+    }
+
+    /// Completes the spatial velocity (the last three components) with the time component,
+    /// so that the velocity is future directed and normalized to -1 for timelike and to 0
+    /// for null geodesics. Returns None if there is no such velocity, e.g. for a timelike
+    /// velocity faster than light.
+    pub fn complete_velocity(&self, position: &[f64; 4], spatial_velocity: &[f64; 3], kind: GeodesicKind) -> Option<[f64; 4]> {
+        let metric = self.components(position);
+        let mut b = 0.0;
+        let mut c = match kind {
+            GeodesicKind::Timelike => 1.0,
+            GeodesicKind::Null => 0.0
+        };
+        for i in 1..4 {
+            b += 2.0 * metric[0][i] * spatial_velocity[i - 1];
+            for j in 1..4 {
+                c += metric[i][j] * spatial_velocity[i - 1] * spatial_velocity[j - 1];
+            }
+        }
+        let a = metric[0][0];
+        let discriminant = b.powi(2) - 4.0 * a * c;
+
+        if discriminant < 0.0 || a == 0.0 {
+            return None;
+        }
+        let first = (-b + discriminant.sqrt()) / (2.0 * a);
+        let second = (-b - discriminant.sqrt()) / (2.0 * a);
+        let time = first.max(second);
+
+        if time <= 0.0 {
+            return None;
+        }
+        Some([time, spatial_velocity[0], spatial_velocity[1], spatial_velocity[2]])
+    }
+
+    fn boyer_lindquist(&self, position: &[f64; 4]) -> Tensor {
+        let r = position[1];
+        let (sin, cos) = position[2].sin_cos();
+        let a = self.spin;
+        let sigma = r.powi(2) + (a * cos).powi(2);
+        let delta = r.powi(2) - 2.0 * r + a.powi(2) + self.charge.powi(2);
+        let potential = (2.0 * r - self.charge.powi(2)) / sigma;
+
+        let mut metric = [[0.0; 4]; 4];
+        metric[0][0] = -(1.0 - potential);
+        metric[0][3] = -a * sin.powi(2) * potential;
+        metric[3][0] = metric[0][3];
+        metric[1][1] = sigma / delta;
+        metric[2][2] = sigma;
+        metric[3][3] = (r.powi(2) + a.powi(2) + (a * sin).powi(2) * potential) * sin.powi(2);
+        metric
+    }
+
+    fn kerr_schild(&self, position: &[f64; 4]) -> Tensor {
+        let (x, y, z) = (position[1], position[2], position[3]);
+        let a = self.spin;
+        let r = self.radius(position);
+        let f = r.powi(2) * (2.0 * r - self.charge.powi(2)) / (r.powi(4) + (a * z).powi(2));
+        let l = [
+            1.0,
+            (r * x + a * y) / (r.powi(2) + a.powi(2)),
+            (r * y - a * x) / (r.powi(2) + a.powi(2)),
+            z / r
+        ];
+
+        let mut metric = [[0.0; 4]; 4];
+        for i in 0..4 {
+            for j in 0..4 {
+                metric[i][j] = f * l[i] * l[j];
+            }
+            metric[i][i] += if i == 0 { -1.0 } else { 1.0 };
+        }
+        metric
+    }
+}
+
+impl BlackHole {
+
+    /// Constructs the metric of the black hole in the given coordinates. Returns None if
+    /// the mass is unknown or if the parameters describe a naked singularity.
+    pub fn metric(&self, coordinates: Coordinates) -> Option<Metric> {
+        self.outer_horizon_radius()?;
+
+        let rg = self.gravitational_radius()?;
+        Some(Metric::new(self.dimensionless_spin()?, self.charge_length_squared().sqrt() / rg, coordinates))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+
+    #[test]
+    fn metric_times_inverse_is_identity() {
+
+        let positions = [
+            (Coordinates::BoyerLindquist, [0.0, 4.0, 1.0, 0.5]),
+            (Coordinates::KerrSchild, [0.0, 3.0, -2.0, 1.5])
+        ];
+
+        for &(coordinates, position) in positions.iter() {
+            let metric = Metric::new(0.9, 0.3, coordinates);
+            let components = metric.components(&position);
+            let inverse = metric.inverse_components(&position);
+
+            for (i, row) in components.iter().enumerate() {
+                for j in 0..4 {
+                    let product: f64 = row.iter().zip(inverse.iter()).map(|(a, b)| a * b[j]).sum();
+                    let expected = if i == j { 1.0 } else { 0.0 };
+                    assert!((product - expected).abs() < 1e-12, "{:?} {} {}", coordinates, i, j);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn schwarzschild_christoffel_symbols() {
+
+        let metric = Metric::new(0.0, 0.0, Coordinates::BoyerLindquist);
+        let r = 10.0;
+        let theta = 1.0;
+        let symbols = metric.christoffel_symbols(&[0.0, r, theta, 0.0]);
+
+        assert!((symbols[1][0][0] - (r - 2.0) / r.powi(3)).abs() < 1e-9);
+        assert!((symbols[0][0][1] - 1.0 / (r * (r - 2.0))).abs() < 1e-9);
+        assert!((symbols[2][1][2] - 1.0 / r).abs() < 1e-9);
+        assert!((symbols[3][2][3] - theta.cos() / theta.sin()).abs() < 1e-9);
+        assert!((symbols[1][2][2] + (r - 2.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn both_coordinates_agree_on_radius_and_static_energy() {
+
+        let boyer_lindquist = Metric::new(0.7, 0.0, Coordinates::BoyerLindquist);
+        let kerr_schild = Metric::new(0.7, 0.0, Coordinates::KerrSchild);
+
+        // a point in the equatorial plane at r = 20
+        let r: f64 = 20.0;
+        let cartesian = [0.0, (r.powi(2) + 0.49_f64).sqrt(), 0.0, 0.0];
+
+        assert!((kerr_schild.radius(&cartesian) - r).abs() < 1e-12);
+        assert!((boyer_lindquist.horizon_radius() - (1.0 + 0.51_f64.sqrt())).abs() < 1e-12);
+        assert!((boyer_lindquist.components(&[0.0, r, FRAC_PI_2, 0.0])[0][0]
+            - kerr_schild.components(&cartesian)[0][0]).abs() < 1e-12);
+    }
+}
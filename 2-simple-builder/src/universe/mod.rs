@@ -1,11 +1,50 @@
 
 extern crate test;
 
+pub mod accretion;
+pub mod binary;
+pub mod circular_orbit;
+pub mod cosmology;
+pub mod energy_extraction;
+pub mod ergosphere;
+pub mod evaporation;
+pub mod geodesic;
+pub mod growth;
+pub mod horizon;
+pub mod jet;
+pub mod lensing;
+pub mod mass_estimation;
+pub mod metric;
+pub mod nbody;
+pub mod orbit_fit;
+pub mod orbits;
+pub mod render;
+pub mod ringdown;
+pub mod shadow;
+pub mod superradiance;
+pub mod thermodynamics;
+pub mod thin_disk;
+pub mod tidal;
+pub mod time_dilation;
+
 use std::f64;
+use std::f64::consts::PI;
+
+use self::mass_estimation::{MassEstimate, MassEstimator};
 
 const DEFAULT_DISCOVERED_BY: &'static str = "Unknown";
 const DEFAULT_DISCOVERED_YEAR: u16 = 2017;
 
+const GRAVITATIONAL_CONSTANT: f64 = 6.674_30e-11; // N*m^2/kg^2
+const SPEED_OF_LIGHT: f64 = 299_792_458.0; // m/s
+const VACUUM_PERMITTIVITY: f64 = 8.854_187_812_8e-12; // F/m
+const REDUCED_PLANCK_CONSTANT: f64 = 1.054_571_817e-34; // J*s
+const BOLTZMANN_CONSTANT: f64 = 1.380_649e-23; // J/K
+const STEFAN_BOLTZMANN_CONSTANT: f64 = 5.670_374_419e-8; // W/(m^2*K^4)
+const PROTON_MASS: f64 = 1.672_621_924e-27; // kg
+const THOMSON_CROSS_SECTION: f64 = 6.652_458_732e-29; // m^2
+const SOLAR_MASS: f64 = 1.988_47e30; // kg
+
 // The type of a black hole
 #[derive(Clone, Debug, PartialEq)]
 pub enum Type {
@@ -15,6 +54,26 @@ pub enum Type {
     Micro
 }
 
+impl Type {
+
+    /// Classifies a black hole by its mass (in kg). Black holes lighter than the sun
+    /// cannot form by stellar collapse and are considered micro black holes, the
+    /// intermediate range spans 100 to 100,000 solar masses.
+    pub fn from_mass(mass: f64) -> Type {
+        let solar_masses = mass / SOLAR_MASS;
+
+        if solar_masses >= 1.0e5 {
+            Type::SuperMassive
+        } else if solar_masses >= 100.0 {
+            Type::IntermediateMassive
+        } else if solar_masses >= 1.0 {
+            Type::Stellar
+        } else {
+            Type::Micro
+        }
+    }
+}
+
 /// The black hole
 ///
 /// # Examples
@@ -43,12 +102,21 @@ pub struct BlackHole {
     /// The mass of the black hole
     pub mass: Option<f64>,
 
+    /// The estimate the mass was derived from (None if it was measured directly)
+    pub mass_estimate: Option<MassEstimate>,
+
     /// The angular momentum (how much does it spin?)
     pub angular_momentum: Option<f64>,
 
     /// The electric charge
     pub electric_charge: Option<f64>,
 
+    /// The magnetic flux threading the event horizon
+    pub magnetic_flux: Option<f64>,
+
+    /// The cosmological redshift (where is it in the universe?)
+    pub redshift: Option<f64>,
+
     /// The type of the black hole
     pub classification: Option<Type>
 }
@@ -63,8 +131,11 @@ pub struct BlackHoleBuilder {
     year_of_discovery: Option<u16>,
 
     mass: Option<f64>,
+    mass_estimate: Option<MassEstimate>,
     angular_momentum: Option<f64>,
     electric_charge: Option<f64>,
+    magnetic_flux: Option<f64>,
+    redshift: Option<f64>,
 
     classification: Option<Type>
 }
@@ -78,8 +149,11 @@ impl BlackHoleBuilder {
             discovered_by:      None,
             year_of_discovery:  None,
             mass:               None,
+            mass_estimate:      None,
             angular_momentum:   None,
             electric_charge:    None,
+            magnetic_flux:      None,
+            redshift:           None,
             classification:     None
         }
     }
@@ -100,11 +174,25 @@ impl BlackHoleBuilder {
         self
     }
 
-    /// Sets the mass
+    /// Sets the (directly measured) mass
     pub fn mass<I>(mut self, mass: I) -> BlackHoleBuilder
         where I: Into<Option<f64>> {
 
         self.mass = mass.into();
+        self.mass_estimate = None;
+        self
+    }
+
+    /// Sets the mass from the given estimator, unless the mass is already known. The
+    /// estimate (including its scatter) is kept on the black hole.
+    pub fn mass_from<E>(mut self, estimator: &E) -> BlackHoleBuilder
+        where E: MassEstimator {
+
+        if self.mass.is_none() {
+            let estimate = estimator.estimate();
+            self.mass = Some(estimate.mass);
+            self.mass_estimate = Some(estimate);
+        }
         self
     }
 
@@ -124,6 +212,22 @@ impl BlackHoleBuilder {
         self
     }
 
+    /// Sets the magnetic flux threading the event horizon
+    pub fn magnetic_flux<I>(mut self, magnetic_flux: I) -> BlackHoleBuilder
+        where I: Into<Option<f64>> {
+
+        self.magnetic_flux = magnetic_flux.into();
+        self
+    }
+
+    /// Sets the cosmological redshift
+    pub fn redshift<I>(mut self, redshift: I) -> BlackHoleBuilder
+        where I: Into<Option<f64>> {
+
+        self.redshift = redshift.into();
+        self
+    }
+
     /// Sets the type of the black hole
     pub fn classification<I>(mut self, classification: I) -> BlackHoleBuilder
         where I: Into<Option<Type>> {
@@ -155,8 +259,11 @@ impl BlackHoleBuilder {
             year_of_discovery:  self.year_of_discovery.unwrap_or(DEFAULT_DISCOVERED_YEAR),
 
             mass:               self.mass,
+            mass_estimate:      self.mass_estimate,
             angular_momentum:   self.angular_momentum,
             electric_charge:    self.electric_charge,
+            magnetic_flux:      self.magnetic_flux,
+            redshift:           self.redshift,
 
             classification:     self.classification
         }
@@ -195,15 +302,168 @@ impl BlackHoleBuilder {
             year_of_discovery:  self.year_of_discovery.unwrap_or(DEFAULT_DISCOVERED_YEAR),
 
             mass:               self.mass,
+            mass_estimate:      self.mass_estimate.clone(),
             angular_momentum:   self.angular_momentum,
             electric_charge:    self.electric_charge,
+            magnetic_flux:      self.magnetic_flux,
+            redshift:           self.redshift,
 
             classification:     self.classification.clone()
         }
     }
 }
 
+impl<'a> From<&'a BlackHole> for BlackHoleBuilder {
+
+    /// Constructs a builder which is preset with all properties of the given black hole.
+    /// This allows to derive modified copies (e.g. after the black hole has grown).
+    fn from(black_hole: &'a BlackHole) -> BlackHoleBuilder {
+        BlackHoleBuilder {
+            name:               black_hole.name.clone(),
+            discovered_by:      Some(black_hole.discovered_by.clone()),
+            year_of_discovery:  Some(black_hole.year_of_discovery),
+            mass:               black_hole.mass,
+            mass_estimate:      black_hole.mass_estimate.clone(),
+            angular_momentum:   black_hole.angular_momentum,
+            electric_charge:    black_hole.electric_charge,
+            magnetic_flux:      black_hole.magnetic_flux,
+            redshift:           black_hole.redshift,
+            classification:     black_hole.classification.clone()
+        }
+    }
+}
+
+/// Integrates the function f over [a, b] using the composite Simpson rule
+/// with the given (even) number of intervals
+fn simpson<F>(f: F, a: f64, b: f64, intervals: usize) -> f64
+    where F: Fn(f64) -> f64 {
+
+    let h = (b - a) / intervals as f64;
+    let inner: f64 = (1..intervals)
+        .map(|i| if i % 2 == 0 { 2.0 } else { 4.0 } * f(a + i as f64 * h))
+        .sum();
+    (f(a) + inner + f(b)) * h / 3.0
+}
+
+/// Inverts the square matrix with Gauss-Jordan elimination and partial pivoting.
+/// Returns None if the matrix is singular.
+fn invert<const N: usize>(matrix: &[[f64; N]; N]) -> Option<[[f64; N]; N]> {
+    let mut left = *matrix;
+    let mut right = [[0.0; N]; N];
+    for (i, row) in right.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+
+    for column in 0..N {
+        let pivot = (column..N)
+            .max_by(|&i, &j| left[i][column].abs().total_cmp(&left[j][column].abs()))
+            .unwrap_or(column);
+        left.swap(column, pivot);
+        right.swap(column, pivot);
+
+        let factor = left[column][column];
+        if factor == 0.0 || !factor.is_finite() {
+            return None;
+        }
+        for k in 0..N {
+            left[column][k] /= factor;
+            right[column][k] /= factor;
+        }
+
+        for row in 0..N {
+            if row != column {
+                let factor = left[row][column];
+                for k in 0..N {
+                    left[row][k] -= factor * left[column][k];
+                    right[row][k] -= factor * right[column][k];
+                }
+            }
+        }
+    }
+    Some(right)
+}
+
 impl BlackHole {
+
+    /// Calculates the gravitational radius, which is half of the Schwarzschild radius.
+    /// All other radii of the black hole are expressed relative to it.
+    ///
+    ///      G * M
+    /// rg = -----
+    ///       c^2
+    ///
+    pub fn gravitational_radius(&self) -> Option<f64> {
+        self.mass.map(|mass| GRAVITATIONAL_CONSTANT * mass / SPEED_OF_LIGHT.powi(2))
+    }
+
+    /// Calculates the spin length (the Kerr parameter) in meters. A black hole
+    /// without angular momentum is treated as non-rotating.
+    ///
+    ///       J
+    /// a = -----
+    ///     M * c
+    ///
+    pub fn spin_length(&self) -> Option<f64> {
+        self.mass.map(|mass| self.angular_momentum.unwrap_or(0.0) / (mass * SPEED_OF_LIGHT))
+    }
+
+    /// Calculates the dimensionless spin, which is 0 for a non-rotating black hole
+    /// and 1 for a maximally rotating one.
+    ///
+    ///       a
+    /// X = ----
+    ///      rg
+    ///
+    pub fn dimensionless_spin(&self) -> Option<f64> {
+        Some(self.spin_length()? / self.gravitational_radius()?)
+    }
+
+    /// Calculates the squared charge length in meters^2. A black hole without
+    /// electric charge is treated as uncharged.
+    ///
+    ///            G * Q^2
+    /// rQ^2 = ---------------
+    ///        4 * pi * e0 * c^4
+    ///
+    pub fn charge_length_squared(&self) -> f64 {
+        let charge = self.electric_charge.unwrap_or(0.0);
+        GRAVITATIONAL_CONSTANT * charge.powi(2)
+            / (4.0 * PI * VACUUM_PERMITTIVITY * SPEED_OF_LIGHT.powi(4))
+    }
+
+    /// Calculates the radius of the outer (event) horizon of the Kerr-Newman solution.
+    /// Returns None if the mass is unknown or if the parameters describe a naked singularity.
+    /// See: https://en.wikipedia.org/wiki/Kerr%E2%80%93Newman_metric
+    ///
+    /// r+ = rg + sqrt(rg^2 - a^2 - rQ^2)
+    ///
+    pub fn outer_horizon_radius(&self) -> Option<f64> {
+        self.horizon_discriminant()
+            .map(|(rg, discriminant)| rg + discriminant)
+    }
+
+    /// Calculates the radius of the inner (Cauchy) horizon of the Kerr-Newman solution.
+    /// Returns None if the mass is unknown or if the parameters describe a naked singularity.
+    ///
+    /// r- = rg - sqrt(rg^2 - a^2 - rQ^2)
+    ///
+    pub fn inner_horizon_radius(&self) -> Option<f64> {
+        self.horizon_discriminant()
+            .map(|(rg, discriminant)| rg - discriminant)
+    }
+
+    /// Returns the gravitational radius together with the square root of the horizon
+    /// discriminant, or None if there is no horizon at all
+    fn horizon_discriminant(&self) -> Option<(f64, f64)> {
+        let rg = self.gravitational_radius()?;
+        let a = self.spin_length()?;
+        let discriminant = rg.powi(2) - a.powi(2) - self.charge_length_squared();
+
+        if discriminant < 0.0 {
+            return None;
+        }
+        Some((rg, discriminant.sqrt()))
+    }
 }
 
 
@@ -256,7 +516,63 @@ mod tests {
         assert_eq!(black_hole_1.electric_charge, black_hole_2.electric_charge);
         assert_eq!(black_hole_1.angular_momentum, black_hole_2.angular_momentum);
     }
-    
+
+    #[test]
+    fn classification_from_mass() {
+
+        assert_eq!(Type::Micro, Type::from_mass(1.0e12));
+        assert_eq!(Type::Stellar, Type::from_mass(10.0 * SOLAR_MASS));
+        assert_eq!(Type::IntermediateMassive, Type::from_mass(1.0e4 * SOLAR_MASS));
+        assert_eq!(Type::SuperMassive, Type::from_mass(4.1e6 * SOLAR_MASS));
+    }
+
+    #[test]
+    fn horizons_of_schwarzschild_black_hole() {
+
+        let black_hole = BlackHoleBuilder::new("Sagittarius A*")
+            .mass(8.26e36)
+            .build();
+
+        let rg = black_hole.gravitational_radius().unwrap();
+        assert!((black_hole.outer_horizon_radius().unwrap() - 2.0 * rg).abs() < 1e-6 * rg);
+        assert_eq!(Some(0.0), black_hole.inner_horizon_radius());
+    }
+
+    #[test]
+    fn horizons_of_kerr_newman_black_hole() {
+
+        let mass = 10.0 * SOLAR_MASS;
+        let rg = GRAVITATIONAL_CONSTANT * mass / SPEED_OF_LIGHT.powi(2);
+        let black_hole = BlackHoleBuilder::new("Gargantua")
+            .mass(mass)
+            .angular_momentum(0.6 * rg * mass * SPEED_OF_LIGHT)
+            .electric_charge(1.0e20)
+            .build();
+
+        let outer = black_hole.outer_horizon_radius().unwrap();
+        let inner = black_hole.inner_horizon_radius().unwrap();
+        let a = black_hole.spin_length().unwrap();
+
+        assert!(outer > inner);
+        assert!((outer + inner - 2.0 * rg).abs() < 1e-9 * rg);
+        assert!((outer * inner - a.powi(2) - black_hole.charge_length_squared()).abs() < 1e-9 * rg.powi(2));
+    }
+
+    #[test]
+    fn naked_singularity_has_no_horizon() {
+
+        let mass = 10.0 * SOLAR_MASS;
+        let rg = GRAVITATIONAL_CONSTANT * mass / SPEED_OF_LIGHT.powi(2);
+        let black_hole = BlackHoleBuilder::new("Naked")
+            .mass(mass)
+            .angular_momentum(1.1 * rg * mass * SPEED_OF_LIGHT)
+            .build();
+
+        assert_eq!(None, black_hole.outer_horizon_radius());
+        assert_eq!(None, black_hole.inner_horizon_radius());
+        assert_eq!(None, BlackHoleBuilder::new("Massless").build().outer_horizon_radius());
+    }
+
     #[bench]
     fn bench_create_black_hole(b: &mut Bencher) {
         b.iter (|| BlackHoleBuilder::new("Gargantua")
@@ -0,0 +1,489 @@
+
+use universe::{BlackHole, BlackHoleBuilder, GRAVITATIONAL_CONSTANT, SPEED_OF_LIGHT};
+use universe::binary::BinaryBlackHole;
+
+pub type Vector = [f64; 3];
+
+/// A black hole moving through space, position in meters and velocity in m/s
+#[derive(Debug, PartialEq)]
+pub struct Body {
+    pub black_hole: BlackHole,
+    pub position: Vector,
+    pub velocity: Vector
+}
+
+impl Body {
+
+    /// Places the black hole at the given position with the given velocity.
+    /// Returns None if the mass of the black hole is unknown.
+    pub fn new(black_hole: BlackHole, position: Vector, velocity: Vector) -> Option<Body> {
+        black_hole.mass?;
+        Some(Body {
+            black_hole,
+            position,
+            velocity
+        })
+    }
+
+    fn mass(&self) -> f64 {
+        self.black_hole.mass.unwrap_or(0.0)
+    }
+
+    /// The radius at which two bodies are considered to have merged
+    fn capture_radius(&self) -> f64 {
+        self.black_hole.outer_horizon_radius()
+            .unwrap_or(2.0 * GRAVITATIONAL_CONSTANT * self.mass() / SPEED_OF_LIGHT.powi(2))
+    }
+}
+
+/// Two bodies which have merged into a remnant (time in seconds since the start)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Merger {
+    pub time: f64,
+    pub primary: String,
+    pub secondary: String,
+    pub remnant: String
+}
+
+/// The Newtonian conserved quantities of the whole system (J, kg*m/s and kg*m^2/s)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Diagnostics {
+    pub energy: f64,
+    pub momentum: Vector,
+    pub angular_momentum: Vector
+}
+
+/// A system of black holes which move under their mutual gravity. Besides the Newtonian
+/// attraction, the first post-Newtonian corrections (Einstein-Infeld-Hoffmann equations)
+/// and the 2.5PN radiation reaction, which lets close pairs inspiral by emitting
+/// gravitational waves, can be switched on. The radiation reaction is added pairwise in the
+/// harmonic gauge. Two black holes whose separation falls below the sum of their horizon
+/// radii are replaced by the remnant of their merger, which moves with their center of mass.
+/// See: https://en.wikipedia.org/wiki/Einstein%E2%80%93Infeld%E2%80%93Hoffmann_equations
+///
+/// # Examples
+///
+/// ```
+/// let mut system = NBodySystem::new(vec![first, second, third])
+///     .radiation_reaction(true);
+///
+/// let mergers = system.evolve(3.15e7).unwrap();
+/// let diagnostics = system.diagnostics();
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct NBodySystem {
+    bodies: Vec<Body>,
+    time: f64,
+    post_newtonian: bool,
+    radiation_reaction: bool,
+    accuracy: f64
+}
+
+impl NBodySystem {
+
+    /// Constructs a system of the given bodies with first post-Newtonian corrections
+    pub fn new(bodies: Vec<Body>) -> NBodySystem {
+        NBodySystem {
+            bodies,
+            time:               0.0,
+            post_newtonian:     true,
+            radiation_reaction: false,
+            accuracy:           1e-3
+        }
+    }
+
+    /// Switches the first post-Newtonian corrections on or off
+    pub fn post_newtonian(mut self, post_newtonian: bool) -> NBodySystem {
+        self.post_newtonian = post_newtonian;
+        self
+    }
+
+    /// Switches the 2.5PN radiation reaction on or off
+    pub fn radiation_reaction(mut self, radiation_reaction: bool) -> NBodySystem {
+        self.radiation_reaction = radiation_reaction;
+        self
+    }
+
+    /// Sets the time step as a fraction of the shortest dynamical time of all pairs
+    pub fn accuracy(mut self, accuracy: f64) -> NBodySystem {
+        self.accuracy = accuracy;
+        self
+    }
+
+    /// The bodies which are currently part of the system
+    pub fn bodies(&self) -> &[Body] {
+        &self.bodies
+    }
+
+    /// The time in seconds since the start of the simulation
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Evolves the system over the given duration in seconds using a fourth order
+    /// Runge-Kutta scheme and returns the mergers which happened in the meantime.
+    /// Returns None if the time step shrinks to zero, which happens when two bodies
+    /// without a horizon (and therefore without a remnant) meet. The system is left
+    /// at the time it reached.
+    pub fn evolve(&mut self, duration: f64) -> Option<Vec<Merger>> {
+        let end = self.time + duration;
+        let mut mergers = Vec::new();
+
+        while self.time < end {
+            let time_step = self.time_step().min(end - self.time);
+
+            if time_step.is_nan() || self.time + time_step <= self.time {
+                return None;
+            }
+            self.step(time_step);
+            self.time += time_step;
+
+            while let Some(merger) = self.merge() {
+                mergers.push(merger);
+            }
+        }
+        Some(mergers)
+    }
+
+    /// Calculates the Newtonian energy, momentum and angular momentum of the system
+    pub fn diagnostics(&self) -> Diagnostics {
+        let mut energy = 0.0;
+        let mut momentum = [0.0; 3];
+        let mut angular_momentum = [0.0; 3];
+
+        for (i, body) in self.bodies.iter().enumerate() {
+            let mass = body.mass();
+            energy += 0.5 * mass * dot(&body.velocity, &body.velocity);
+            momentum = add(&momentum, &scale(&body.velocity, mass));
+            angular_momentum = add(&angular_momentum,
+                                   &scale(&cross(&body.position, &body.velocity), mass));
+
+            for other in &self.bodies[i + 1..] {
+                energy -= GRAVITATIONAL_CONSTANT * mass * other.mass()
+                    / norm(&sub(&body.position, &other.position));
+            }
+        }
+        Diagnostics { energy, momentum, angular_momentum }
+    }
+
+    /// The step size resolves the shortest orbital or encounter time of all pairs
+    fn time_step(&self) -> f64 {
+        let mut shortest = f64::INFINITY;
+
+        for (i, body) in self.bodies.iter().enumerate() {
+            for other in &self.bodies[i + 1..] {
+                let distance = norm(&sub(&body.position, &other.position));
+                let speed = norm(&sub(&body.velocity, &other.velocity));
+                let orbital = (distance.powi(3)
+                    / (GRAVITATIONAL_CONSTANT * (body.mass() + other.mass()))).sqrt();
+
+                shortest = shortest.min(orbital).min(distance / speed);
+            }
+        }
+        self.accuracy * shortest
+    }
+
+    fn step(&mut self, h: f64) {
+        let masses: Vec<f64> = self.bodies.iter().map(|body| body.mass()).collect();
+        let positions: Vec<Vector> = self.bodies.iter().map(|body| body.position).collect();
+        let velocities: Vec<Vector> = self.bodies.iter().map(|body| body.velocity).collect();
+
+        let derivative = |positions: &[Vector], velocities: &[Vector]| {
+            (velocities.to_vec(), self.accelerations(&masses, positions, velocities))
+        };
+        let shift = |state: &[Vector], rates: &[Vector], factor: f64| -> Vec<Vector> {
+            state.iter().zip(rates).map(|(x, rate)| add(x, &scale(rate, factor))).collect()
+        };
+
+        let (k1x, k1v) = derivative(&positions, &velocities);
+        let (k2x, k2v) = derivative(&shift(&positions, &k1x, h / 2.0), &shift(&velocities, &k1v, h / 2.0));
+        let (k3x, k3v) = derivative(&shift(&positions, &k2x, h / 2.0), &shift(&velocities, &k2v, h / 2.0));
+        let (k4x, k4v) = derivative(&shift(&positions, &k3x, h), &shift(&velocities, &k3v, h));
+
+        for (i, body) in self.bodies.iter_mut().enumerate() {
+            let dx = add(&add(&k1x[i], &scale(&k2x[i], 2.0)), &add(&scale(&k3x[i], 2.0), &k4x[i]));
+            let dv = add(&add(&k1v[i], &scale(&k2v[i], 2.0)), &add(&scale(&k3v[i], 2.0), &k4v[i]));
+            body.position = add(&body.position, &scale(&dx, h / 6.0));
+            body.velocity = add(&body.velocity, &scale(&dv, h / 6.0));
+        }
+    }
+
+    /// Calculates the accelerations of all bodies. With nij = (xi - xj) / rij and the Newtonian
+    /// potential phi_i = Sum G * mk / rik the first post-Newtonian equations of motion are
+    ///
+    ///            G * mj          1                                        7
+    /// ai = Sum ------ * (-nij + --- * (Aij * nij + Bij * (vi - vj)) + ----- * rij * aj)
+    ///           rij^2           c^2                                    2*c^2
+    ///
+    /// Aij = 4*phi_i + phi_j + 1/2*rij*nij.aj + 3/2*(nij.vj)^2 + 4*vi.vj - vi^2 - 2*vj^2
+    /// Bij = nij.(4*vi - 3*vj)
+    ///
+    /// and the radiation reaction on the relative motion of a pair is
+    ///
+    ///     8       (G*M)^2      (        17*G*M                  3*G*M     )
+    /// a = - * eta ------- * ((3*v^2 + ------) * rdot * n - (v^2 + -----) * v)
+    ///     5       c^5*r^3      (         3*r                      r       )
+    ///
+    fn accelerations(&self, masses: &[f64], positions: &[Vector], velocities: &[Vector]) -> Vec<Vector> {
+        let count = masses.len();
+        let c2 = SPEED_OF_LIGHT.powi(2);
+
+        let newtonian: Vec<Vector> = (0..count)
+            .map(|i| (0..count).filter(|&j| j != i).fold([0.0; 3], |sum, j| {
+                let separation = sub(&positions[i], &positions[j]);
+                let distance = norm(&separation);
+                add(&sum, &scale(&separation, -GRAVITATIONAL_CONSTANT * masses[j] / distance.powi(3)))
+            }))
+            .collect();
+
+        let mut accelerations = newtonian.clone();
+
+        if self.post_newtonian {
+            let potentials: Vec<f64> = (0..count)
+                .map(|i| (0..count).filter(|&k| k != i)
+                    .map(|k| GRAVITATIONAL_CONSTANT * masses[k] / norm(&sub(&positions[i], &positions[k])))
+                    .sum())
+                .collect();
+
+            for i in 0..count {
+                for j in (0..count).filter(|&j| j != i) {
+                    let separation = sub(&positions[i], &positions[j]);
+                    let distance = norm(&separation);
+                    let n = scale(&separation, 1.0 / distance);
+                    let (vi, vj) = (&velocities[i], &velocities[j]);
+                    let gm = GRAVITATIONAL_CONSTANT * masses[j];
+
+                    let bracket = dot(vi, vi) + 2.0 * dot(vj, vj) - 4.0 * dot(vi, vj)
+                        - 1.5 * dot(&n, vj).powi(2) - 4.0 * potentials[i] - potentials[j]
+                        - 0.5 * distance * dot(&n, &newtonian[j]);
+                    let correction = add(
+                        &add(&scale(&n, -gm / distance.powi(2) * bracket),
+                             &scale(&sub(vi, vj),
+                                    gm / distance.powi(2) * dot(&n, &sub(&scale(vi, 4.0), &scale(vj, 3.0))))),
+                        &scale(&newtonian[j], 3.5 * gm / distance));
+
+                    accelerations[i] = add(&accelerations[i], &scale(&correction, 1.0 / c2));
+                }
+            }
+        }
+
+        if self.radiation_reaction {
+            for i in 0..count {
+                for j in i + 1..count {
+                    let separation = sub(&positions[i], &positions[j]);
+                    let distance = norm(&separation);
+                    let n = scale(&separation, 1.0 / distance);
+                    let v = sub(&velocities[i], &velocities[j]);
+                    let total = masses[i] + masses[j];
+                    let gm = GRAVITATIONAL_CONSTANT * total;
+                    let eta = masses[i] * masses[j] / total.powi(2);
+
+                    let factor = 1.6 * eta * gm.powi(2) / (SPEED_OF_LIGHT.powi(5) * distance.powi(3));
+                    let relative = scale(&sub(
+                        &scale(&n, (3.0 * dot(&v, &v) + 17.0 / 3.0 * gm / distance) * dot(&n, &v)),
+                        &scale(&v, dot(&v, &v) + 3.0 * gm / distance)), factor);
+
+                    accelerations[i] = add(&accelerations[i], &scale(&relative, masses[j] / total));
+                    accelerations[j] = sub(&accelerations[j], &scale(&relative, masses[i] / total));
+                }
+            }
+        }
+        accelerations
+    }
+
+    /// Replaces the first pair of bodies within each other's horizons by their remnant.
+    /// Bodies for which no remnant can be computed (e.g. due to a super-extremal spin)
+    /// are not merged and stay in the system.
+    fn merge(&mut self) -> Option<Merger> {
+        let count = self.bodies.len();
+        let (i, j, remnant) = (0..count)
+            .flat_map(|i| (i + 1..count).map(move |j| (i, j)))
+            .filter(|&(i, j)| {
+                let (first, second) = (&self.bodies[i], &self.bodies[j]);
+                norm(&sub(&first.position, &second.position)) < first.capture_radius() + second.capture_radius()
+            })
+            .find_map(|(i, j)| {
+                let (first, second) = (&self.bodies[i].black_hole, &self.bodies[j].black_hole);
+                let name = format!("{} + {}", first.name, second.name);
+                let binary = BinaryBlackHole::new(BlackHoleBuilder::from(first).build(),
+                                                  BlackHoleBuilder::from(second).build());
+
+                binary.remnant(&name).map(|remnant| (i, j, remnant))
+            })?;
+
+        let second = self.bodies.remove(j);
+        let first = self.bodies.remove(i);
+        let (first_mass, second_mass) = (first.mass(), second.mass());
+        let total = first_mass + second_mass;
+        let position = scale(&add(&scale(&first.position, first_mass), &scale(&second.position, second_mass)), 1.0 / total);
+        let velocity = scale(&add(&scale(&first.velocity, first_mass), &scale(&second.velocity, second_mass)), 1.0 / total);
+
+        let merger = Merger {
+            time:       self.time,
+            primary:    first.black_hole.name,
+            secondary:  second.black_hole.name,
+            remnant:    remnant.name.clone()
+        };
+
+        self.bodies.insert(i, Body { black_hole: remnant, position, velocity });
+        Some(merger)
+    }
+}
+
+fn add(a: &Vector, b: &Vector) -> Vector {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: &Vector, b: &Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: &Vector, factor: f64) -> Vector {
+    [a[0] * factor, a[1] * factor, a[2] * factor]
+}
+
+fn dot(a: &Vector, b: &Vector) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: &Vector, b: &Vector) -> Vector {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+fn norm(a: &Vector) -> f64 {
+    dot(a, a).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, Type};
+
+    fn body(name: &str, solar_masses: f64, position: Vector, velocity: Vector) -> Body {
+        let black_hole = BlackHoleBuilder::new(name)
+            .mass(solar_masses * SOLAR_MASS)
+            .classification(Type::from_mass(solar_masses * SOLAR_MASS))
+            .build();
+        Body::new(black_hole, position, velocity).unwrap()
+    }
+
+    /// Two bodies on a circular orbit around their center of mass
+    fn circular_pair(first: &str, second: &str, solar_masses: f64, separation: f64, center: Vector, drift: Vector) -> Vec<Body> {
+        let speed = (GRAVITATIONAL_CONSTANT * 2.0 * solar_masses * SOLAR_MASS / separation).sqrt() / 2.0;
+        vec![
+            body(first, solar_masses, add(&center, &[separation / 2.0, 0.0, 0.0]), add(&drift, &[0.0, speed, 0.0])),
+            body(second, solar_masses, add(&center, &[-separation / 2.0, 0.0, 0.0]), add(&drift, &[0.0, -speed, 0.0]))
+        ]
+    }
+
+    #[test]
+    fn bodies_without_remnant_are_kept() {
+
+        let mass = 10.0 * SOLAR_MASS;
+        let naked = BlackHoleBuilder::new("X")
+            .mass(mass)
+            .angular_momentum(1.5 * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT)
+            .build();
+        let bodies = vec![
+            body("A", 10.0, [0.0; 3], [0.0; 3]),
+            Body::new(naked, [1.0, 0.0, 0.0], [0.0; 3]).unwrap(),
+            body("B", 10.0, [2.0, 0.0, 0.0], [0.0; 3])
+        ];
+
+        let mut system = NBodySystem::new(bodies);
+        let merger = system.merge().unwrap();
+
+        assert_eq!(("A", "B", "A + B"), (&merger.primary[..], &merger.secondary[..], &merger.remnant[..]));
+        assert_eq!(2, system.bodies().len());
+        assert!(system.bodies().iter().any(|body| body.black_hole.name == "X"));
+        assert_eq!(None, system.merge());
+    }
+
+    #[test]
+    fn coincident_bodies_without_horizon_stop_the_integration() {
+
+        let mass = 10.0 * SOLAR_MASS;
+        let naked = |name: &str| BlackHoleBuilder::new(name)
+            .mass(mass)
+            .angular_momentum(1.5 * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT)
+            .build();
+        let bodies = vec![
+            Body::new(naked("X"), [0.0; 3], [0.0; 3]).unwrap(),
+            Body::new(naked("Y"), [0.0; 3], [0.0; 3]).unwrap()
+        ];
+
+        let mut system = NBodySystem::new(bodies);
+
+        assert_eq!(None, system.evolve(1.0));
+        assert_eq!(0.0, system.time());
+        assert_eq!(2, system.bodies().len());
+    }
+
+    #[test]
+    fn newtonian_triple_conserves_energy_and_angular_momentum() {
+
+        let mut bodies = circular_pair("A", "B", 10.0, 1.0e9, [0.0; 3], [0.0, -1.0e3, 0.0]);
+        bodies.push(body("C", 10.0, [2.0e10, 0.0, 0.0], [0.0, 2.0e4, 0.0]));
+
+        let mut system = NBodySystem::new(bodies).post_newtonian(false);
+        let before = system.diagnostics();
+        let mergers = system.evolve(2.0e4).unwrap();
+        let after = system.diagnostics();
+
+        assert!(mergers.is_empty());
+        assert!(((after.energy - before.energy) / before.energy).abs() < 1e-8);
+        assert!(((after.angular_momentum[2] - before.angular_momentum[2]) / before.angular_momentum[2]).abs() < 1e-8);
+    }
+
+    #[test]
+    fn radiation_reaction_drives_binary_to_merger() {
+
+        let solar_masses = 30.0;
+        let rg = GRAVITATIONAL_CONSTANT * 2.0 * solar_masses * SOLAR_MASS / SPEED_OF_LIGHT.powi(2);
+        let separation = 12.0 * rg;
+        let drift = [1.0e4, 0.0, 0.0];
+
+        let mut system = NBodySystem::new(circular_pair("GW-A", "GW-B", solar_masses, separation, [0.0; 3], drift))
+            .post_newtonian(false)
+            .radiation_reaction(true);
+        let momentum = system.diagnostics().momentum;
+
+        let binary = BinaryBlackHole::new(
+            BlackHoleBuilder::new("A").mass(solar_masses * SOLAR_MASS).build(),
+            BlackHoleBuilder::new("B").mass(solar_masses * SOLAR_MASS).build());
+        let expected = binary.time_to_merger(separation).unwrap();
+
+        let mergers = system.evolve(2.0 * expected).unwrap();
+
+        assert_eq!(1, mergers.len());
+        assert_eq!("GW-A + GW-B", mergers[0].remnant);
+        assert!((mergers[0].time / expected - 1.0).abs() < 0.02, "{} != {}", mergers[0].time, expected);
+
+        let remnant = &system.bodies()[0];
+        assert_eq!(1, system.bodies().len());
+        assert!(remnant.black_hole.mass.unwrap() < 2.0 * solar_masses * SOLAR_MASS);
+        assert!(remnant.black_hole.dimensionless_spin().unwrap() > 0.6);
+        assert!((remnant.velocity[0] - drift[0]).abs() < 1e-6 * drift[0]);
+        assert!((momentum[0] / (2.0 * solar_masses * SOLAR_MASS) - drift[0]).abs() < 1e-6 * drift[0]);
+    }
+
+    #[test]
+    fn post_newtonian_binary_stays_close_to_newtonian() {
+
+        let solar_masses = 10.0;
+        let rg = GRAVITATIONAL_CONSTANT * 2.0 * solar_masses * SOLAR_MASS / SPEED_OF_LIGHT.powi(2);
+        let separation = 1000.0 * rg;
+        let mut system = NBodySystem::new(circular_pair("A", "B", solar_masses, separation, [0.0; 3], [0.0; 3]));
+
+        let period = 2.0 * ::std::f64::consts::PI
+            * (separation.powi(3) / (GRAVITATIONAL_CONSTANT * 2.0 * solar_masses * SOLAR_MASS)).sqrt();
+        let before = system.diagnostics();
+        system.evolve(period).unwrap();
+        let after = system.diagnostics();
+
+        // the 1PN orbit still conserves the Newtonian quantities up to O(v^2/c^2)
+        assert!(((after.energy - before.energy) / before.energy).abs() < 1e-2);
+        assert!(((after.angular_momentum[2] - before.angular_momentum[2]) / before.angular_momentum[2]).abs() < 1e-2);
+    }
+}
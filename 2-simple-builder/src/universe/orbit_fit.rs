@@ -0,0 +1,490 @@
+
+use std::f64::consts::{LN_10, PI};
+
+use universe::{BlackHoleBuilder, GRAVITATIONAL_CONSTANT, invert};
+use universe::mass_estimation::{EstimationMethod, MassEstimate, MassEstimator};
+
+const PARAMETERS: usize = 8;
+const MAX_ECCENTRICITY: f64 = 0.9999;
+
+pub type Covariance = [[f64; PARAMETERS]; PARAMETERS];
+
+/// The position of a star relative to the black hole at a given time (in seconds), as
+/// angular offsets in radians towards the north (declination) and the east (right ascension)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AstrometricObservation {
+    pub time: f64,
+    pub declination: f64,
+    pub right_ascension: f64,
+    pub uncertainty: f64
+}
+
+/// The line-of-sight velocity of a star in m/s (positive if receding) at a given time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RadialVelocityObservation {
+    pub time: f64,
+    pub velocity: f64,
+    pub uncertainty: f64
+}
+
+/// The Keplerian elements of the orbit of a star around a black hole together with the
+/// distance to the black hole. Times are measured in seconds, angles in radians and the
+/// distance in meters. The semi-major axis is the angle it subtends on the sky.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitalElements {
+    pub period: f64,
+    pub eccentricity: f64,
+    pub periapsis_time: f64,
+    pub semi_major_axis: f64,
+    pub inclination: f64,
+    pub ascending_node: f64,
+    pub argument_of_periapsis: f64,
+    pub distance: f64
+}
+
+impl OrbitalElements {
+
+    /// Calculates the mass of the black hole in kg from Kepler's third law
+    ///
+    ///     4 * pi^2 * (a * R0)^3
+    /// M = ---------------------
+    ///            G * P^2
+    ///
+    pub fn mass(&self) -> f64 {
+        4.0 * PI.powi(2) * (self.semi_major_axis * self.distance).powi(3)
+            / (GRAVITATIONAL_CONSTANT * self.period.powi(2))
+    }
+
+    /// Calculates the angular offsets (north, east) of the star at the given time using the
+    /// Thiele-Innes constants A, B, F and G
+    ///
+    /// dec = A * X + F * Y,  ra = B * X + G * Y
+    /// X = cos(E) - e,  Y = sqrt(1 - e^2) * sin(E)
+    ///
+    pub fn position(&self, time: f64) -> (f64, f64) {
+        let anomaly = self.eccentric_anomaly(time);
+        let x = anomaly.cos() - self.eccentricity;
+        let y = (1.0 - self.eccentricity.powi(2)).sqrt() * anomaly.sin();
+
+        let (sin_node, cos_node) = self.ascending_node.sin_cos();
+        let (sin_periapsis, cos_periapsis) = self.argument_of_periapsis.sin_cos();
+        let cos_inclination = self.inclination.cos();
+        let a = self.semi_major_axis;
+
+        let thiele_innes_a = a * (cos_periapsis * cos_node - sin_periapsis * sin_node * cos_inclination);
+        let thiele_innes_b = a * (cos_periapsis * sin_node + sin_periapsis * cos_node * cos_inclination);
+        let thiele_innes_f = a * (-sin_periapsis * cos_node - cos_periapsis * sin_node * cos_inclination);
+        let thiele_innes_g = a * (-sin_periapsis * sin_node + cos_periapsis * cos_node * cos_inclination);
+
+        (thiele_innes_a * x + thiele_innes_f * y, thiele_innes_b * x + thiele_innes_g * y)
+    }
+
+    /// Calculates the radial velocity of the star in m/s at the given time
+    ///
+    ///       2 * pi * a * R0 * sin(i)
+    /// vz = ------------------------- * (cos(omega + nu) + e * cos(omega))
+    ///        P * sqrt(1 - e^2)
+    ///
+    pub fn radial_velocity(&self, time: f64) -> f64 {
+        let anomaly = self.eccentric_anomaly(time);
+        let e = self.eccentricity;
+        let true_anomaly = 2.0 * ((1.0 + e).sqrt() * (anomaly / 2.0).sin())
+            .atan2((1.0 - e).sqrt() * (anomaly / 2.0).cos());
+        let amplitude = 2.0 * PI * self.semi_major_axis * self.distance * self.inclination.sin()
+            / (self.period * (1.0 - e.powi(2)).sqrt());
+
+        amplitude * ((self.argument_of_periapsis + true_anomaly).cos() + e * self.argument_of_periapsis.cos())
+    }
+
+    /// Solves Kepler's equation M = E - e * sin(E) with Newton's method
+    fn eccentric_anomaly(&self, time: f64) -> f64 {
+        let mean_anomaly = 2.0 * PI * ((time - self.periapsis_time) / self.period).rem_euclid(1.0);
+        let mut anomaly = if self.eccentricity > 0.8 { PI } else { mean_anomaly };
+
+        for _ in 0..50 {
+            let step = (anomaly - self.eccentricity * anomaly.sin() - mean_anomaly)
+                / (1.0 - self.eccentricity * anomaly.cos());
+            anomaly -= step;
+            if step.abs() < 1e-14 {
+                break;
+            }
+        }
+        anomaly
+    }
+
+    fn to_parameters(self) -> [f64; PARAMETERS] {
+        [self.period, self.eccentricity, self.periapsis_time, self.semi_major_axis,
+         self.inclination, self.ascending_node, self.argument_of_periapsis, self.distance]
+    }
+
+    fn from_parameters(parameters: &[f64; PARAMETERS]) -> OrbitalElements {
+        OrbitalElements {
+            period:                 parameters[0].abs(),
+            eccentricity:           parameters[1].clamp(0.0, MAX_ECCENTRICITY),
+            periapsis_time:         parameters[2],
+            semi_major_axis:        parameters[3].abs(),
+            inclination:            parameters[4],
+            ascending_node:         parameters[5],
+            argument_of_periapsis:  parameters[6],
+            distance:               parameters[7].abs()
+        }
+    }
+}
+
+/// The best-fit orbit together with the covariance of its elements (in the order period,
+/// eccentricity, periapsis time, semi-major axis, inclination, ascending node, argument of
+/// periapsis and distance)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OrbitFit {
+    pub elements: OrbitalElements,
+    pub covariance: Covariance,
+    pub chi_squared: f64,
+    pub degrees_of_freedom: usize
+}
+
+impl OrbitFit {
+
+    /// The mass of the black hole in kg
+    pub fn mass(&self) -> f64 {
+        self.elements.mass()
+    }
+
+    /// The distance to the black hole in m
+    pub fn distance(&self) -> f64 {
+        self.elements.distance
+    }
+
+    /// Calculates the covariance matrix of mass and distance by propagating the covariance
+    /// of the elements (the mass depends on the period, the semi-major axis and the distance)
+    pub fn mass_distance_covariance(&self) -> [[f64; 2]; 2] {
+        let mass = self.mass();
+        let mut gradients = [[0.0; PARAMETERS]; 2];
+        gradients[0][0] = -2.0 * mass / self.elements.period;
+        gradients[0][3] = 3.0 * mass / self.elements.semi_major_axis;
+        gradients[0][7] = 3.0 * mass / self.elements.distance;
+        gradients[1][7] = 1.0;
+
+        let mut covariance = [[0.0; 2]; 2];
+        for (row, first) in covariance.iter_mut().zip(gradients.iter()) {
+            for (value, second) in row.iter_mut().zip(gradients.iter()) {
+                *value = (0..PARAMETERS)
+                    .flat_map(|i| (0..PARAMETERS).map(move |j| (i, j)))
+                    .map(|(i, j)| first[i] * self.covariance[i][j] * second[j])
+                    .sum();
+            }
+        }
+        covariance
+    }
+
+    /// Sets the fitted mass on the given builder
+    pub fn apply(&self, builder: BlackHoleBuilder) -> BlackHoleBuilder {
+        builder.mass(self.mass())
+    }
+}
+
+/// The fitted mass as a dynamical estimate, whose scatter is the statistical uncertainty
+/// of the mass in dex
+impl MassEstimator for OrbitFit {
+    fn estimate(&self) -> MassEstimate {
+        MassEstimate {
+            mass:       self.mass(),
+            scatter:    self.mass_distance_covariance()[0][0].sqrt() / (self.mass() * LN_10),
+            method:     EstimationMethod::Dynamical
+        }
+    }
+}
+
+/// Fits a Keplerian orbit to astrometric and radial velocity observations of a star
+/// orbiting a black hole (like the S-stars around Sagittarius A*) with the
+/// Levenberg-Marquardt algorithm. The astrometry fixes the shape and the angular size of
+/// the orbit, the radial velocities its physical size, so both together measure the mass
+/// of and the distance to the black hole. The black hole is assumed to be at rest at the
+/// origin of the astrometric reference frame.
+/// See: https://en.wikipedia.org/wiki/Levenberg%E2%80%93Marquardt_algorithm
+///
+/// # Examples
+///
+/// ```
+/// let fit = OrbitFitter::new(astrometry, radial_velocities)
+///     .fit(initial_guess)
+///     .unwrap();
+///
+/// let sagittarius = fit.apply(BlackHoleBuilder::new("Sagittarius A*")).build();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct OrbitFitter {
+    astrometry: Vec<AstrometricObservation>,
+    radial_velocities: Vec<RadialVelocityObservation>,
+    max_iterations: usize,
+    tolerance: f64
+}
+
+impl OrbitFitter {
+
+    /// Constructs a fitter for the given observations
+    pub fn new(astrometry: Vec<AstrometricObservation>, radial_velocities: Vec<RadialVelocityObservation>) -> OrbitFitter {
+        OrbitFitter {
+            astrometry,
+            radial_velocities,
+            max_iterations: 200,
+            tolerance:      1e-10
+        }
+    }
+
+    /// Sets the maximal number of iterations
+    pub fn max_iterations(mut self, max_iterations: usize) -> OrbitFitter {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the relative change of chi^2 below which the fit is considered converged. Changes
+    /// of chi^2 are measured relative to 1 once it drops below 1, since the observations do
+    /// not constrain the orbit any better.
+    pub fn tolerance(mut self, tolerance: f64) -> OrbitFitter {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Fits the orbit starting from the given guess. Returns None if there are fewer
+    /// observations than parameters or the fit does not converge within the maximal number
+    /// of iterations.
+    pub fn fit(&self, initial: OrbitalElements) -> Option<OrbitFit> {
+        let observations = 2 * self.astrometry.len() + self.radial_velocities.len();
+        if observations <= PARAMETERS {
+            return None;
+        }
+
+        let mut parameters = initial.to_parameters();
+        let mut chi_squared = self.chi_squared(&parameters);
+        let mut damping = 1e-3;
+        let mut converged = false;
+
+        for _ in 0..self.max_iterations {
+            let (curvature, gradient) = self.normal_equations(&parameters);
+            let mut damped = curvature;
+            for (i, row) in damped.iter_mut().enumerate() {
+                row[i] += damping * curvature[i][i];
+            }
+            let inverse = invert(&damped)?;
+
+            let mut candidate = parameters;
+            for (value, row) in candidate.iter_mut().zip(inverse.iter()) {
+                *value -= row.iter().zip(gradient.iter()).map(|(a, b)| a * b).sum::<f64>();
+            }
+            candidate = OrbitalElements::from_parameters(&candidate).to_parameters();
+
+            // close to the minimum chi^2 stops changing, whether the step is accepted or not
+            let candidate_chi_squared = self.chi_squared(&candidate);
+            converged = (chi_squared - candidate_chi_squared).abs() <= self.tolerance * chi_squared.max(1.0);
+
+            if candidate_chi_squared < chi_squared {
+                parameters = candidate;
+                chi_squared = candidate_chi_squared;
+                damping = (damping / 10.0).max(1e-12);
+            } else {
+                damping *= 10.0;
+            }
+            if converged || damping > 1e12 {
+                break;
+            }
+        }
+
+        if !converged {
+            return None;
+        }
+
+        let (curvature, _) = self.normal_equations(&parameters);
+        Some(OrbitFit {
+            elements:           OrbitalElements::from_parameters(&parameters),
+            covariance:         invert(&curvature)?,
+            chi_squared,
+            degrees_of_freedom: observations - PARAMETERS
+        })
+    }
+
+    /// The residuals of all observations divided by their uncertainties
+    fn residuals(&self, parameters: &[f64; PARAMETERS]) -> Vec<f64> {
+        let elements = OrbitalElements::from_parameters(parameters);
+        let mut residuals = Vec::with_capacity(2 * self.astrometry.len() + self.radial_velocities.len());
+
+        for observation in &self.astrometry {
+            let (declination, right_ascension) = elements.position(observation.time);
+            residuals.push((declination - observation.declination) / observation.uncertainty);
+            residuals.push((right_ascension - observation.right_ascension) / observation.uncertainty);
+        }
+        for observation in &self.radial_velocities {
+            residuals.push((elements.radial_velocity(observation.time) - observation.velocity)
+                / observation.uncertainty);
+        }
+        residuals
+    }
+
+    fn chi_squared(&self, parameters: &[f64; PARAMETERS]) -> f64 {
+        self.residuals(parameters).iter().map(|residual| residual.powi(2)).sum()
+    }
+
+    /// Calculates J^T * J and J^T * r, the Jacobian J is computed with central differences
+    fn normal_equations(&self, parameters: &[f64; PARAMETERS]) -> (Covariance, [f64; PARAMETERS]) {
+        let residuals = self.residuals(parameters);
+        let mut jacobian = Vec::with_capacity(PARAMETERS);
+
+        for i in 0..PARAMETERS {
+            let step = match i {
+                1 | 4 | 5 | 6 => 1e-7,
+                2 => 1e-7 * parameters[0],
+                _ => 1e-7 * parameters[i].abs()
+            };
+            let mut forward = *parameters;
+            let mut backward = *parameters;
+            forward[i] += step;
+            backward[i] -= step;
+
+            let derivative: Vec<f64> = self.residuals(&forward).iter()
+                .zip(self.residuals(&backward).iter())
+                .map(|(f, b)| (f - b) / (2.0 * step))
+                .collect();
+            jacobian.push(derivative);
+        }
+
+        let mut curvature = [[0.0; PARAMETERS]; PARAMETERS];
+        let mut gradient = [0.0; PARAMETERS];
+        for i in 0..PARAMETERS {
+            gradient[i] = jacobian[i].iter().zip(residuals.iter()).map(|(a, b)| a * b).sum();
+            for j in 0..PARAMETERS {
+                curvature[i][j] = jacobian[i].iter().zip(jacobian[j].iter()).map(|(a, b)| a * b).sum();
+            }
+        }
+        (curvature, gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::SOLAR_MASS;
+
+    const JULIAN_YEAR: f64 = 31_557_600.0;
+    const ARCSECOND: f64 = PI / 648_000.0;
+    const PARSEC: f64 = 3.085_677_581e16;
+
+    /// The orbit of S2 around Sagittarius A* (GRAVITY collaboration 2018)
+    fn s2() -> OrbitalElements {
+        OrbitalElements {
+            period:                 16.05 * JULIAN_YEAR,
+            eccentricity:           0.884,
+            periapsis_time:         18.38 * JULIAN_YEAR,
+            semi_major_axis:        0.1255 * ARCSECOND,
+            inclination:            134.18_f64.to_radians(),
+            ascending_node:         228.07_f64.to_radians(),
+            argument_of_periapsis:  66.13_f64.to_radians(),
+            distance:               8_120.0 * PARSEC
+        }
+    }
+
+    fn observe(orbit: &OrbitalElements) -> OrbitFitter {
+        observe_with_noise(orbit, || 0.0)
+    }
+
+    /// Observes the orbit, every measurement is shifted by the given number of standard deviations
+    fn observe_with_noise<N: FnMut() -> f64>(orbit: &OrbitalElements, mut noise: N) -> OrbitFitter {
+        let astrometric_uncertainty = 0.5e-3 * ARCSECOND;
+        let astrometry = (0..80).map(|i| {
+            let time = i as f64 * 0.3 * JULIAN_YEAR;
+            let (declination, right_ascension) = orbit.position(time);
+            AstrometricObservation {
+                time,
+                declination:        declination + noise() * astrometric_uncertainty,
+                right_ascension:    right_ascension + noise() * astrometric_uncertainty,
+                uncertainty:        astrometric_uncertainty
+            }
+        }).collect();
+        let radial_velocities = (0..30).map(|i| {
+            let time = (16.0 + 0.2 * i as f64) * JULIAN_YEAR;
+            RadialVelocityObservation { time, velocity: orbit.radial_velocity(time) + noise() * 10_000.0, uncertainty: 10_000.0 }
+        }).collect();
+
+        OrbitFitter::new(astrometry, radial_velocities)
+    }
+
+    /// Standard normal deviates from a linear congruential generator and the Box-Muller
+    /// transform, so the noise is the same in every run
+    fn gaussian_noise(seed: u64) -> impl FnMut() -> f64 {
+        let mut state = seed;
+        let mut uniform = move || {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            ((state >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        };
+        move || (-2.0 * uniform().ln()).sqrt() * (2.0 * PI * uniform()).cos()
+    }
+
+    fn perturbed(truth: &OrbitalElements) -> OrbitalElements {
+        let mut guess = *truth;
+        guess.period *= 1.01;
+        guess.eccentricity = 0.87;
+        guess.semi_major_axis *= 0.98;
+        guess.inclination += 0.02;
+        guess.ascending_node -= 0.02;
+        guess.argument_of_periapsis += 0.02;
+        guess.distance *= 1.05;
+        guess
+    }
+
+    #[test]
+    fn s2_orbit_weighs_sagittarius_a() {
+
+        let truth = s2();
+        assert!((truth.mass() / SOLAR_MASS / 4.1e6 - 1.0).abs() < 0.05);
+
+        let fit = observe(&truth).fit(perturbed(&truth)).unwrap();
+
+        assert!((fit.mass() / truth.mass() - 1.0).abs() < 1e-6, "{}", fit.mass() / SOLAR_MASS);
+        assert!((fit.distance() / truth.distance - 1.0).abs() < 1e-6);
+        assert!(fit.chi_squared < 1e-6);
+        assert_eq!(160 + 30 - 8, fit.degrees_of_freedom);
+
+        let black_hole = fit.apply(BlackHoleBuilder::new("Sagittarius A*")).build();
+        assert_eq!(Some(fit.mass()), black_hole.mass);
+    }
+
+    #[test]
+    fn noisy_observations_recover_the_mass_within_its_uncertainty() {
+
+        let truth = s2();
+        let fit = observe_with_noise(&truth, gaussian_noise(2018)).fit(perturbed(&truth)).unwrap();
+        let mass_error = fit.mass_distance_covariance()[0][0].sqrt();
+        let reduced_chi_squared = fit.chi_squared / fit.degrees_of_freedom as f64;
+
+        assert!((fit.mass() - truth.mass()).abs() < 3.0 * mass_error, "{} +/- {}", fit.mass(), mass_error);
+        assert!(reduced_chi_squared > 0.7 && reduced_chi_squared < 1.3, "{}", reduced_chi_squared);
+    }
+
+    #[test]
+    fn fit_without_convergence_fails() {
+
+        let truth = s2();
+
+        assert!(observe(&truth).max_iterations(1).fit(perturbed(&truth)).is_none());
+        assert!(observe(&truth).max_iterations(0).fit(truth).is_none());
+    }
+
+    #[test]
+    fn mass_and_distance_are_correlated() {
+
+        let truth = s2();
+        let fit = observe(&truth).fit(truth).unwrap();
+        let covariance = fit.mass_distance_covariance();
+
+        let mass_error = covariance[0][0].sqrt() / fit.mass();
+        let distance_error = covariance[1][1].sqrt() / fit.distance();
+        let correlation = covariance[0][1] / (covariance[0][0] * covariance[1][1]).sqrt();
+
+        assert!(mass_error > 1e-4 && mass_error < 0.1, "{}", mass_error);
+        assert!(distance_error > 1e-4 && distance_error < 0.1, "{}", distance_error);
+        assert!(correlation > 0.5, "{}", correlation);
+
+        let estimate = fit.estimate();
+        assert_eq!(EstimationMethod::Dynamical, estimate.method);
+        assert!((estimate.scatter - mass_error / LN_10).abs() < 1e-12);
+    }
+}
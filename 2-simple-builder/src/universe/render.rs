@@ -0,0 +1,396 @@
+
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::thread;
+
+use universe::BlackHole;
+use universe::circular_orbit::OrbitDirection;
+use universe::geodesic::{GeodesicIntegrator, Termination};
+use universe::metric::{Coordinates, GeodesicKind, Metric};
+
+const DEFAULT_TOLERANCE: f64 = 1e-7;
+const MINIMUM_INCLINATION: f64 = 1e-3;
+const CHECKERBOARD_SIZE: f64 = PI / 18.0;
+const SHADOW_COLOR: [u8; 3] = [0, 0, 0];
+const BACKGROUND_COLORS: [[u8; 3]; 2] = [[20, 20, 36], [44, 44, 70]];
+
+/// The camera which looks at the black hole. Distances are measured in units of the
+/// gravitational radius, angles in radians.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+
+    /// The width of the image in pixels
+    pub width: usize,
+
+    /// The height of the image in pixels
+    pub height: usize,
+
+    /// The distance from the black hole, which should be far outside of the photon sphere
+    pub distance: f64,
+
+    /// The angle between the rotation axis of the black hole and the line of sight.
+    /// A camera on the rotation axis is moved off the axis by a milliradian, where the
+    /// Boyer-Lindquist coordinates are singular.
+    pub inclination: f64,
+
+    /// The horizontal field of view
+    pub field_of_view: f64
+}
+
+impl Camera {
+
+    /// Constructs a new camera
+    pub fn new(width: usize, height: usize, distance: f64, inclination: f64, field_of_view: f64) -> Camera {
+        Camera {
+            width,
+            height,
+            distance,
+            inclination,
+            field_of_view
+        }
+    }
+}
+
+/// A geometrically thin, optically thick accretion disk in the equatorial plane of the
+/// black hole, which rotates on prograde circular orbits. Radii are measured in units of
+/// the gravitational radius.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AccretionDisk {
+    pub inner_radius: f64,
+    pub outer_radius: f64
+}
+
+impl AccretionDisk {
+
+    /// Constructs a new accretion disk
+    pub fn new(inner_radius: f64, outer_radius: f64) -> AccretionDisk {
+        AccretionDisk {
+            inner_radius,
+            outer_radius
+        }
+    }
+
+    /// Constructs an accretion disk which reaches down to the innermost stable circular orbit
+    /// of the given black hole. Returns None if the black hole has no such orbit.
+    pub fn around(black_hole: &BlackHole, outer_radius: f64) -> Option<AccretionDisk> {
+        let isco = black_hole.isco_radius(OrbitDirection::Prograde)? / black_hole.gravitational_radius()?;
+        Some(AccretionDisk::new(isco, outer_radius))
+    }
+
+    /// The emitted flux of a thin disk, which vanishes at the inner edge (up to constants)
+    ///
+    /// F ~ r^-3 * (1 - sqrt(rin / r))
+    ///
+    fn flux(&self, radius: f64) -> f64 {
+        (1.0 - (self.inner_radius / radius).sqrt()) / radius.powi(3)
+    }
+
+    /// The flux at its maximum, which is used to normalize the brightness
+    fn peak_flux(&self) -> f64 {
+        self.flux(49.0 / 36.0 * self.inner_radius)
+    }
+}
+
+/// A rendered image with 8 bit RGB pixels, stored row by row from the top left
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 3]>
+}
+
+impl Image {
+
+    /// Returns the pixel in the given column and row
+    pub fn pixel(&self, column: usize, row: usize) -> [u8; 3] {
+        self.pixels[row * self.width + column]
+    }
+
+    /// Writes the image in the binary PPM format
+    /// See: http://netpbm.sourceforge.net/doc/ppm.html
+    pub fn write_ppm<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(writer, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for pixel in &self.pixels {
+            writer.write_all(pixel)?;
+        }
+        writer.flush()
+    }
+
+    /// Saves the image as PPM file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write_ppm(&mut BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Renders the shadow, the photon ring and the accretion disk of a black hole by tracing
+/// a light ray backwards in time from every pixel of the camera. The rows of the image
+/// are distributed over several threads.
+///
+/// # Examples
+///
+/// ```
+/// let image = Renderer::new(&gargantua, Camera::new(640, 360, 1000.0, 1.5, 0.05))
+///     .unwrap()
+///     .disk(AccretionDisk::around(&gargantua, 20.0))
+///     .threads(8)
+///     .render();
+///
+/// image.save("gargantua.ppm").unwrap();
+/// ```
+pub struct Renderer {
+    metric: Metric,
+    camera: Camera,
+    disk: Option<AccretionDisk>,
+    threads: usize,
+    tolerance: f64
+}
+
+impl Renderer {
+
+    /// Constructs a new renderer without accretion disk, which uses all available CPUs.
+    /// Returns None if the mass of the black hole is unknown or if the parameters describe
+    /// a naked singularity.
+    pub fn new(black_hole: &BlackHole, camera: Camera) -> Option<Renderer> {
+        Some(Renderer {
+            metric:     black_hole.metric(Coordinates::BoyerLindquist)?,
+            camera,
+            disk:       None,
+            threads:    thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1),
+            tolerance:  DEFAULT_TOLERANCE
+        })
+    }
+
+    /// Sets the accretion disk
+    pub fn disk<I>(mut self, disk: I) -> Renderer
+        where I: Into<Option<AccretionDisk>> {
+
+        self.disk = disk.into();
+        self
+    }
+
+    /// Sets the number of threads
+    pub fn threads(mut self, threads: usize) -> Renderer {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Sets the error tolerance of the geodesic integrator
+    pub fn tolerance(mut self, tolerance: f64) -> Renderer {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Renders the image. A camera without pixels renders an empty image.
+    pub fn render(&self) -> Image {
+        let width = self.camera.width;
+        let mut pixels = vec![SHADOW_COLOR; width * self.camera.height];
+        let rows_per_thread = self.camera.height.div_ceil(self.threads).max(1);
+
+        // without pixels there is nothing to distribute over the threads
+        if !pixels.is_empty() {
+            thread::scope(|scope| {
+                for (chunk, rows) in pixels.chunks_mut(rows_per_thread * width).enumerate() {
+                    scope.spawn(move || {
+                        for (index, pixel) in rows.iter_mut().enumerate() {
+                            let row = chunk * rows_per_thread + index / width;
+                            *pixel = self.trace_pixel(index % width, row);
+                        }
+                    });
+                }
+            });
+        }
+
+        Image {
+            width,
+            height: self.camera.height,
+            pixels
+        }
+    }
+
+    /// Traces the light ray which arrives in the given pixel backwards and determines its color
+    fn trace_pixel(&self, column: usize, row: usize) -> [u8; 3] {
+        let camera = &self.camera;
+        let scale = camera.field_of_view / camera.width as f64;
+        let horizontal = (column as f64 + 0.5 - camera.width as f64 / 2.0) * scale;
+        let vertical = (camera.height as f64 / 2.0 - row as f64 - 0.5) * scale;
+
+        // the camera looks at the origin from the x-z plane, the rotation axis points up
+        let inclination = camera.inclination.clamp(MINIMUM_INCLINATION, PI - MINIMUM_INCLINATION);
+        let (sin, cos) = inclination.sin_cos();
+        let origin = [camera.distance * sin, 0.0, camera.distance * cos];
+        let direction = [-sin - vertical * cos, horizontal, -cos + vertical * sin];
+
+        let (position, spatial) = spherical(&origin, &direction);
+        let velocity = match self.metric.complete_velocity(&position, &spatial, GeodesicKind::Null) {
+            Some(velocity) => velocity,
+            None => return SHADOW_COLOR
+        };
+        // the light ray arrives at the camera, so it is traced into the past
+        let velocity = [-velocity[0], -velocity[1], -velocity[2], -velocity[3]];
+
+        let mut color = None;
+        let integrator = GeodesicIntegrator::new()
+            .tolerance(self.tolerance)
+            .initial_step(camera.distance * 1e-3)
+            .escape_radius(camera.distance * 1.2);
+        let mut last = (position, velocity);
+
+        let termination = integrator.trace(&self.metric, position, velocity, f64::INFINITY, |previous, next| {
+            last = (next.position, next.velocity);
+
+            let (before, after) = (previous.position[2] - FRAC_PI_2, next.position[2] - FRAC_PI_2);
+            if let Some(disk) = self.disk {
+                if before * after <= 0.0 && before != after {
+                    let fraction = before / (before - after);
+                    let radius = previous.position[1] + fraction * (next.position[1] - previous.position[1]);
+
+                    if radius >= disk.inner_radius && radius <= disk.outer_radius {
+                        color = Some(self.disk_color(&disk, radius, &next.position, &next.velocity));
+                        return false;
+                    }
+                }
+            }
+            true
+        });
+
+        match (color, termination) {
+            (Some(color), _) => color,
+            (None, Termination::Escape) => background_color(&last.0),
+            (None, _) => SHADOW_COLOR
+        }
+    }
+
+    /// The color of the disk at the given radius as seen by the camera. The observed flux is
+    /// boosted by the fourth power of the redshift factor, which makes the approaching side
+    /// of the disk brighter than the receding one (Doppler beaming).
+    fn disk_color(&self, disk: &AccretionDisk, radius: f64, position: &[f64; 4], velocity: &[f64; 4]) -> [u8; 3] {
+        let spin = self.metric.spin;
+        let angular_velocity = 1.0 / (radius.powf(1.5) + spin);
+        let potential = radius.powf(1.5) - 3.0 * radius.sqrt() + 2.0 * spin;
+
+        if potential <= 0.0 {
+            return SHADOW_COLOR;
+        }
+        let time = (radius.powf(1.5) + spin) / (radius.powf(0.75) * potential.sqrt());
+
+        let momentum = self.metric.lower(position, velocity);
+        let redshift = momentum[0] / (time * (momentum[0] + angular_velocity * momentum[3]));
+        let brightness = redshift.powi(4) * disk.flux(radius) / disk.peak_flux();
+
+        heat_color(1.0 - (-2.0 * brightness).exp())
+    }
+}
+
+/// Converts a Cartesian position and direction into Boyer-Lindquist coordinates, which
+/// far away from the black hole coincide with spherical coordinates
+fn spherical(origin: &[f64; 3], direction: &[f64; 3]) -> ([f64; 4], [f64; 3]) {
+    let (x, y, z) = (origin[0], origin[1], origin[2]);
+    let (vx, vy, vz) = (-direction[0], -direction[1], -direction[2]);
+
+    let r = (x * x + y * y + z * z).sqrt();
+    let rho = (x * x + y * y).sqrt();
+    let radial = (x * vx + y * vy + z * vz) / r;
+    let polar = (z * radial - r * vz) / (r * rho);
+    let azimuthal = (x * vy - y * vx) / rho.powi(2);
+
+    ([0.0, r, (z / r).acos(), y.atan2(x)], [radial, polar, azimuthal])
+}
+
+/// A checkerboard on the celestial sphere, which makes the lensing visible
+fn background_color(position: &[f64; 4]) -> [u8; 3] {
+    let polar = (position[2] / CHECKERBOARD_SIZE).floor() as i64;
+    let azimuthal = (position[3] / CHECKERBOARD_SIZE).floor() as i64;
+
+    BACKGROUND_COLORS[(polar + azimuthal).rem_euclid(2) as usize]
+}
+
+/// Maps a brightness between 0 and 1 to black, red, yellow and white
+fn heat_color(brightness: f64) -> [u8; 3] {
+    let channel = |offset: f64| ((3.0 * brightness - offset).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [channel(0.0), channel(1.0), channel(2.0)]
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, SPEED_OF_LIGHT, Type};
+
+    fn gargantua(spin: f64) -> BlackHole {
+        let mass = 1.0e8 * SOLAR_MASS;
+        let rg = BlackHoleBuilder::new("Gargantua").mass(mass).build().gravitational_radius().unwrap();
+
+        BlackHoleBuilder::new("Gargantua")
+            .discovered_by("Dr. Mann".to_string())
+            .mass(mass)
+            .angular_momentum(spin * rg * mass * SPEED_OF_LIGHT)
+            .classification(Type::SuperMassive)
+            .build()
+    }
+
+    fn brightness(pixel: [u8; 3]) -> u32 {
+        pixel.iter().map(|&channel| u32::from(channel)).sum()
+    }
+
+    #[test]
+    fn shadow_in_front_of_background() {
+
+        let image = Renderer::new(&gargantua(0.0), Camera::new(15, 9, 100.0, 1.2, 0.3))
+            .unwrap()
+            .threads(4)
+            .render();
+
+        assert_eq!(SHADOW_COLOR, image.pixel(7, 4));
+        assert!(BACKGROUND_COLORS.contains(&image.pixel(0, 0)));
+
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n15 9\n255\n"));
+        assert_eq!(b"P6\n15 9\n255\n".len() + 15 * 9 * 3, ppm.len());
+    }
+
+    #[test]
+    fn face_on_shadow() {
+
+        for &inclination in &[0.0, PI] {
+            let image = Renderer::new(&gargantua(0.5), Camera::new(9, 9, 100.0, inclination, 0.3))
+                .unwrap()
+                .threads(3)
+                .render();
+
+            assert_eq!(SHADOW_COLOR, image.pixel(4, 4));
+            assert!(BACKGROUND_COLORS.contains(&image.pixel(0, 0)));
+            assert!(BACKGROUND_COLORS.contains(&image.pixel(8, 8)));
+        }
+    }
+
+    #[test]
+    fn empty_image() {
+
+        let image = Renderer::new(&gargantua(0.0), Camera::new(0, 9, 100.0, 1.2, 0.3)).unwrap().render();
+
+        assert_eq!((0, 9), (image.width, image.height));
+        assert!(image.pixels.is_empty());
+        assert!(Renderer::new(&gargantua(0.0), Camera::new(9, 0, 100.0, 1.2, 0.3)).unwrap().render().pixels.is_empty());
+    }
+
+    #[test]
+    fn approaching_side_of_the_disk_is_brighter() {
+
+        let black_hole = gargantua(0.9);
+        let image = Renderer::new(&black_hole, Camera::new(24, 9, 100.0, 1.3, 0.4))
+            .unwrap()
+            .disk(AccretionDisk::around(&black_hole, 15.0))
+            .render();
+
+        let half = |columns: ::std::ops::Range<usize>| -> u32 {
+            columns.flat_map(|column| (0..9).map(move |row| (column, row)))
+                .map(|(column, row)| brightness(image.pixel(column, row)))
+                .sum()
+        };
+
+        assert!(half(0..12) > 2 * half(12..24));
+    }
+}
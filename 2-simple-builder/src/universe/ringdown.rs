@@ -0,0 +1,148 @@
+
+use std::f64::consts::PI;
+
+use universe::{BlackHole, SPEED_OF_LIGHT};
+
+/// Fits of the quasi-normal mode frequencies of Kerr black holes by Berti, Cardoso & Will
+/// (2006), Phys. Rev. D 73, 064030 (table VIII). Each row contains the mode (l, m, n) and
+/// the coefficients f1, f2, f3, q1, q2, q3 of
+///
+/// M * omega = f1 + f2 * (1 - X)^f3
+/// Q         = q1 + q2 * (1 - X)^q3
+///
+const MODE_FITS: [((u8, u8, u8), [f64; 6]); 6] = [
+    ((2, 2, 0), [1.5251, -1.1568, 0.1292,  0.7000, 1.4187, -0.4990]),
+    ((2, 2, 1), [1.3673, -1.0260, 0.1628,  0.1000, 0.5436, -0.4731]),
+    ((2, 2, 2), [1.3223, -1.0257, 0.1860, -0.1000, 0.4206, -0.4256]),
+    ((2, 1, 0), [0.6000, -0.2339, 0.4175, -0.3000, 2.3561, -0.2277]),
+    ((3, 3, 0), [1.8956, -1.3043, 0.1818,  0.9000, 2.3430, -0.4810]),
+    ((4, 4, 0), [2.3000, -1.5056, 0.2244,  1.1929, 3.1191, -0.4825])
+];
+
+/// A quasi-normal mode of a perturbed black hole, i.e. one of the damped oscillations
+/// emitted as gravitational waves while the black hole settles down (the ringdown)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuasiNormalMode {
+
+    /// The polar index
+    pub l: u8,
+
+    /// The azimuthal index
+    pub m: u8,
+
+    /// The overtone index (0 for the fundamental mode)
+    pub n: u8,
+
+    /// The oscillation frequency in Hz
+    pub frequency: f64,
+
+    /// The time in seconds in which the amplitude decays by a factor of e
+    pub damping_time: f64,
+
+    /// The quality factor, roughly the number of oscillations before the mode has decayed
+    pub quality_factor: f64
+}
+
+/// Ringdown of a perturbed black hole. The charge of the black hole is not taken
+/// into account.
+impl BlackHole {
+
+    /// Calculates the quasi-normal mode with the given indices. Returns None if the mass
+    /// is unknown, the spin exceeds the maximal value or there is no fit for the mode.
+    ///
+    ///         M * omega     c^3
+    /// f   = ----------- * -----
+    ///          2 * pi     G * M
+    ///
+    ///         2 * Q       G * M
+    /// tau = --------- * -----
+    ///       M * omega    c^3
+    ///
+    pub fn quasi_normal_mode(&self, l: u8, m: u8, n: u8) -> Option<QuasiNormalMode> {
+        let time_unit = self.gravitational_radius()? / SPEED_OF_LIGHT;
+        let spin = self.dimensionless_spin()?.abs();
+        let &(_, [f1, f2, f3, q1, q2, q3]) = MODE_FITS.iter()
+            .find(|&&(mode, _)| mode == (l, m, n))?;
+
+        if spin > 1.0 {
+            return None;
+        }
+
+        let omega = f1 + f2 * (1.0 - spin).powf(f3);
+        let quality_factor = q1 + q2 * (1.0 - spin).powf(q3);
+
+        Some(QuasiNormalMode {
+            l,
+            m,
+            n,
+            frequency:      omega / (2.0 * PI * time_unit),
+            damping_time:   2.0 * quality_factor / omega * time_unit,
+            quality_factor
+        })
+    }
+
+    /// Calculates all quasi-normal modes for which fits are available: the l = m = 2
+    /// fundamental mode and its first two overtones, followed by the l = 2, m = 1 and
+    /// the l = m = 3 and l = m = 4 fundamental modes
+    pub fn quasi_normal_modes(&self) -> Option<Vec<QuasiNormalMode>> {
+        MODE_FITS.iter()
+            .map(|&((l, m, n), _)| self.quasi_normal_mode(l, m, n))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, Type};
+
+    fn remnant(mass: f64, spin: f64) -> BlackHole {
+        let rg = BlackHoleBuilder::new("GW150914").mass(mass).build().gravitational_radius().unwrap();
+
+        BlackHoleBuilder::new("GW150914")
+            .discovered_by("LIGO".to_string())
+            .year_of_discovery(2015)
+            .mass(mass)
+            .angular_momentum(spin * rg * mass * SPEED_OF_LIGHT)
+            .classification(Type::Stellar)
+            .build()
+    }
+
+    #[test]
+    fn schwarzschild_fundamental_mode() {
+
+        // Leaver (1985): M * omega = 0.3737 - 0.0890 i for l = m = 2, the fits are accurate
+        // to a few percent at zero spin
+        let black_hole = remnant(10.0 * SOLAR_MASS, 0.0);
+        let time_unit = black_hole.gravitational_radius().unwrap() / SPEED_OF_LIGHT;
+        let fundamental = black_hole.quasi_normal_mode(2, 2, 0).unwrap();
+
+        assert!((fundamental.frequency * 2.0 * PI * time_unit / 0.373_7 - 1.0).abs() < 0.02);
+        assert!((fundamental.damping_time / time_unit * 0.089_0 - 1.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn gw150914_ringdown() {
+
+        // the 62 solar mass remnant at z = 0.09 rang down at 251 Hz with a damping time of 4 ms
+        // in the detector frame
+        // See: Abbott et al. (2016), Phys. Rev. Lett. 116, 221101
+        let fundamental = remnant(62.0 * 1.09 * SOLAR_MASS, 0.67).quasi_normal_mode(2, 2, 0).unwrap();
+
+        assert!((fundamental.frequency - 251.0).abs() < 5.0, "{}", fundamental.frequency);
+        assert!((fundamental.damping_time - 4.0e-3).abs() < 0.2e-3, "{}", fundamental.damping_time);
+    }
+
+    #[test]
+    fn overtones_decay_faster() {
+
+        let modes = remnant(62.0 * SOLAR_MASS, 0.67).quasi_normal_modes().unwrap();
+
+        assert_eq!(6, modes.len());
+        assert!(modes[1].damping_time < modes[0].damping_time);
+        assert!(modes[2].damping_time < modes[1].damping_time);
+        assert_eq!(None, remnant(62.0 * SOLAR_MASS, 0.67).quasi_normal_mode(5, 5, 0));
+        assert_eq!(None, BlackHoleBuilder::new("Unknown").build().quasi_normal_modes());
+    }
+}
@@ -0,0 +1,332 @@
+
+use std::f64::consts::PI;
+
+use universe::BlackHole;
+use universe::circular_orbit::OrbitDirection;
+use universe::cosmology::{microarcseconds, Cosmology};
+
+const CONTOUR_POINTS: usize = 720;
+const MAX_CHORD: f64 = 5e-3; // gravitational radii
+const MAX_REFINEMENTS: usize = 60;
+const MIN_SPIN: f64 = 1e-6;
+const MIN_INCLINATION: f64 = 1e-6;
+
+/// The outline of the shadow of a black hole on the sky. The contour consists of points
+/// (alpha, beta), where alpha is measured perpendicular to the projected spin axis and beta
+/// along it. The shadow of a rotating black hole is flattened on the side which moves
+/// towards the observer and shifted away from the center.
+///
+/// The mean diameter, the displacement of the center and the asymmetry (the root mean
+/// square deviation from a circle, times two) follow Johannsen & Psaltis (2010), ApJ 718, 446
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shadow {
+    pub contour: Vec<(f64, f64)>,
+    pub diameter: f64,
+    pub displacement: f64,
+    pub asymmetry: f64
+}
+
+impl Shadow {
+
+    /// Multiplies all lengths with the given factor (e.g. to convert them into angles)
+    pub fn scale(self, factor: f64) -> Shadow {
+        Shadow {
+            contour:        self.contour.iter().map(|&(alpha, beta)| (alpha * factor, beta * factor)).collect(),
+            diameter:       self.diameter * factor,
+            displacement:   self.displacement * factor,
+            asymmetry:      self.asymmetry * factor
+        }
+    }
+
+    /// The width of the shadow perpendicular to the spin axis
+    pub fn width(&self) -> f64 {
+        let (min, max) = alpha_range(&self.contour);
+        max - min
+    }
+
+    /// Calculates the mean radius and the asymmetry around the center of the contour
+    ///
+    ///        1                                   1
+    /// Rm = ------ * integral R dphi,  A = 2 * sqrt(------ * integral (R - Rm)^2 dphi)
+    ///      2 * pi                                2 * pi
+    ///
+    fn from_contour(contour: Vec<(f64, f64)>) -> Shadow {
+        let (min, max) = alpha_range(&contour);
+        let center = (min + max) / 2.0;
+
+        let mut polar: Vec<(f64, f64)> = contour.iter()
+            .map(|&(alpha, beta)| (beta.atan2(alpha - center), beta.hypot(alpha - center)))
+            .collect();
+        polar.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let average = |f: &dyn Fn(f64) -> f64| {
+            let integral: f64 = polar.iter()
+                .zip(polar.iter().cycle().skip(1))
+                .map(|(&(phi, radius), &(next_phi, next_radius))| {
+                    let step = (next_phi - phi).rem_euclid(2.0 * PI);
+                    (f(radius) + f(next_radius)) / 2.0 * step
+                })
+                .sum();
+            integral / (2.0 * PI)
+        };
+        let mean_radius = average(&|radius| radius);
+        let asymmetry = 2.0 * average(&|radius| (radius - mean_radius).powi(2)).sqrt();
+
+        Shadow {
+            contour,
+            diameter:       2.0 * mean_radius,
+            displacement:   center,
+            asymmetry
+        }
+    }
+}
+
+/// The shadow of a black hole: the dark region on the sky, bounded by the light rays which
+/// approach the unstable spherical photon orbits asymptotically. The inclination is the angle
+/// between the spin axis and the line of sight (in radians).
+/// See: Bardeen (1973), Timelike and null geodesics in the Kerr metric
+///
+/// Only uncharged (Kerr) black holes are supported. All methods return None if the mass is
+/// unknown, the black hole carries an electric charge or has no horizon.
+impl BlackHole {
+
+    /// Calculates the shadow in units of the gravitational radius. A spherical photon orbit
+    /// of radius r has the impact parameters
+    ///
+    ///      r^2 * (3 - r) - a^2 * (r + 1)         r^3 * (4 * a^2 - r * (r - 3)^2)
+    /// xi = -----------------------------,  eta = -------------------------------
+    ///               a * (r - 1)                          a^2 * (r - 1)^2
+    ///
+    /// and appears on the sky at
+    ///
+    ///           xi
+    /// alpha = - ----,  beta = +- sqrt(eta + a^2 * cos(i)^2 - xi^2 * cot(i)^2)
+    ///          sin(i)
+    ///
+    pub fn shadow(&self, inclination: f64) -> Option<Shadow> {
+        if self.charge_length_squared() != 0.0 {
+            return None;
+        }
+        self.outer_horizon_radius()?;
+
+        let rg = self.gravitational_radius()?;
+        let spin = self.dimensionless_spin()?;
+        let prograde = self.photon_orbit_radius(OrbitDirection::Prograde)? / rg;
+        let retrograde = self.photon_orbit_radius(OrbitDirection::Retrograde)? / rg;
+
+        if spin.abs() < MIN_SPIN {
+            let radius = 27.0_f64.sqrt();
+            return Some(Shadow::from_contour((0..CONTOUR_POINTS)
+                .map(|i| 2.0 * PI * i as f64 / CONTOUR_POINTS as f64)
+                .map(|phi| (radius * phi.cos(), radius * phi.sin()))
+                .collect()));
+        }
+
+        let a = spin.abs();
+        let inclination = inclination.clamp(MIN_INCLINATION, PI - MIN_INCLINATION);
+        let (sin_i, cos_i) = inclination.sin_cos();
+        let impact_parameter = |r: f64| (r.powi(2) * (3.0 - r) - a.powi(2) * (r + 1.0)) / (a * (r - 1.0));
+        let project = |r: f64| {
+            let xi = impact_parameter(r);
+            let eta = r.powi(3) * (4.0 * a.powi(2) - r * (r - 3.0).powi(2)) / (a * (r - 1.0)).powi(2);
+            (-xi / sin_i, eta + (a * cos_i).powi(2) - (xi * cos_i / sin_i).powi(2))
+        };
+
+        // the photon orbit with xi = 0 is always visible, away from the equator the orbits
+        // close to the prograde and retrograde circular orbits are hidden
+        let visible = |r: f64| project(r).1 >= 0.0;
+        let polar = bisect(&|r| impact_parameter(r) >= 0.0, retrograde, prograde);
+        let lower = if visible(prograde) { prograde } else { bisect(&visible, prograde, polar) };
+        let upper = if visible(retrograde) { retrograde } else { bisect(&visible, retrograde, polar) };
+
+        let point = |r: f64| {
+            let (alpha, beta_squared) = project(r);
+            (spin.signum() * alpha, beta_squared.max(0.0).sqrt())
+        };
+        let upper_half = sample_contour(&point, lower, upper);
+        let lower_half = upper_half.iter().rev().map(|&(alpha, beta)| (alpha, -beta));
+
+        Some(Shadow::from_contour(upper_half.iter().cloned().chain(lower_half).collect()))
+    }
+
+    /// Calculates the shadow as seen by an observer at the given distance in meters,
+    /// with all lengths in microarcseconds
+    pub fn apparent_shadow(&self, inclination: f64, distance: f64) -> Option<Shadow> {
+        let rg = self.gravitational_radius()?;
+        self.shadow(inclination).map(|shadow| shadow.scale(microarcseconds(rg / distance)))
+    }
+
+    /// Calculates the shadow as seen from earth, with all lengths in microarcseconds.
+    /// The distance follows from the redshift of the black hole in the given cosmology.
+    /// Returns None if the redshift is unknown.
+    pub fn apparent_shadow_at_redshift(&self, inclination: f64, cosmology: &Cosmology) -> Option<Shadow> {
+        self.apparent_shadow(inclination, cosmology.angular_diameter_distance(self.redshift?))
+    }
+}
+
+/// The smallest and largest horizontal coordinate of a contour
+fn alpha_range(contour: &[(f64, f64)]) -> (f64, f64) {
+    contour.iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(alpha, _)| (min.min(alpha), max.max(alpha)))
+}
+
+/// Samples the curve traced by the given function between both parameters. Starting from a
+/// grid which is denser at both ends, intervals are halved until neighbouring points are
+/// less than MAX_CHORD apart, so the points are spread evenly along the curve. This matters
+/// close to the maximal spin, where a tiny range of photon orbits near the horizon traces
+/// the flattened edge of the shadow.
+fn sample_contour<F>(point: &F, lower: f64, upper: f64) -> Vec<(f64, f64)>
+    where F: Fn(f64) -> (f64, f64) {
+
+    let mut samples: Vec<(f64, (f64, f64))> = (0..=CONTOUR_POINTS / 2)
+        .map(|i| lower + (upper - lower) * (1.0 - (PI * i as f64 / (CONTOUR_POINTS / 2) as f64).cos()) / 2.0)
+        .map(|r| (r, point(r)))
+        .collect();
+
+    for _ in 0..MAX_REFINEMENTS {
+        let mut refined = Vec::with_capacity(2 * samples.len());
+        for pair in samples.windows(2) {
+            let ((start, first), (end, second)) = (pair[0], pair[1]);
+            refined.push(pair[0]);
+            if (second.0 - first.0).hypot(second.1 - first.1) > MAX_CHORD {
+                let middle = (start + end) / 2.0;
+                refined.push((middle, point(middle)));
+            }
+        }
+        refined.extend(samples.last().cloned());
+
+        let done = refined.len() == samples.len();
+        samples = refined;
+        if done {
+            break;
+        }
+    }
+    samples.into_iter().map(|(_, point)| point).collect()
+}
+
+/// Finds the boundary of the region where the condition holds, starting from a point
+/// outside and a point inside of it. Returns the last point inside.
+fn bisect<F>(condition: &F, mut outside: f64, mut inside: f64) -> f64
+    where F: Fn(f64) -> bool {
+
+    for _ in 0..60 {
+        let middle = (outside + inside) / 2.0;
+        if condition(middle) {
+            inside = middle;
+        } else {
+            outside = middle;
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+    use universe::{BlackHoleBuilder, GRAVITATIONAL_CONSTANT, SOLAR_MASS, SPEED_OF_LIGHT};
+
+    fn black_hole(spin: f64) -> BlackHole {
+        let mass = 6.5e9 * SOLAR_MASS;
+        BlackHoleBuilder::new("M87*")
+            .mass(mass)
+            .angular_momentum(spin * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT)
+            .redshift(0.004_283)
+            .build()
+    }
+
+    #[test]
+    fn schwarzschild_shadow_is_a_circle() {
+
+        let shadow = black_hole(0.0).shadow(1.0).unwrap();
+
+        assert!((shadow.diameter - 2.0 * 27.0_f64.sqrt()).abs() < 1e-3);
+        assert!(shadow.asymmetry < 1e-6);
+        assert!(shadow.displacement.abs() < 1e-9);
+    }
+
+    #[test]
+    fn rapidly_rotating_shadow_is_flattened_and_shifted() {
+
+        let edge_on = black_hole(0.998).shadow(FRAC_PI_2).unwrap();
+        let face_on = black_hole(0.998).shadow(0.0).unwrap();
+
+        // for an extremal black hole the contour extends from alpha = -2 to alpha = 7
+        assert!((edge_on.width() - 9.0).abs() < 0.2, "{}", edge_on.width());
+        assert!(edge_on.displacement > 2.0 && edge_on.displacement < 2.6, "{}", edge_on.displacement);
+        assert!(edge_on.asymmetry > 0.3, "{}", edge_on.asymmetry);
+        assert!(face_on.asymmetry < 1e-3, "{}", face_on.asymmetry);
+
+        // the diameter changes by only a few percent with spin and inclination
+        let circle = 2.0 * 27.0_f64.sqrt();
+        assert!(edge_on.diameter < circle && edge_on.diameter > 0.92 * circle, "{}", edge_on.diameter);
+        assert!(face_on.diameter < circle && face_on.diameter > 0.92 * circle, "{}", face_on.diameter);
+
+        let counter_rotating = black_hole(-0.998).shadow(FRAC_PI_2).unwrap();
+        assert!((counter_rotating.displacement + edge_on.displacement).abs() < 1e-9);
+    }
+
+    #[test]
+    fn extremal_shadow_matches_the_analytic_contour() {
+
+        // the edge-on contour of an extremal black hole has a vertical edge at alpha = -2
+        // between beta = -sqrt(3) and beta = sqrt(3), the face-on one is a circle with a
+        // radius of 2 * (1 + sqrt(2))
+        // See: Chandrasekhar (1983), The Mathematical Theory of Black Holes, section 63
+        let edge_on = black_hole(1.0).shadow(FRAC_PI_2).unwrap();
+        let face_on = black_hole(1.0).shadow(0.0).unwrap();
+
+        assert!((edge_on.width() - 9.0).abs() < 1e-2, "{}", edge_on.width());
+        assert!((edge_on.displacement - 2.5).abs() < 1e-2, "{}", edge_on.displacement);
+        assert!(edge_on.contour.iter().any(|&(alpha, beta)| alpha < -1.99 && beta.abs() > 1.7));
+        assert!((face_on.diameter - 4.0 * (1.0 + 2.0_f64.sqrt())).abs() < 1e-3, "{}", face_on.diameter);
+
+        // the contour converges smoothly towards the extremal limit
+        let nearly_extremal = black_hole(0.999_999).shadow(FRAC_PI_2).unwrap();
+        assert!((nearly_extremal.diameter - edge_on.diameter).abs() < 5e-3);
+        assert!((nearly_extremal.asymmetry - edge_on.asymmetry).abs() < 5e-3);
+    }
+
+    #[test]
+    fn slowly_rotating_shadow_is_displaced() {
+
+        // to first order in spin the circle is shifted by 2 * a * sin(i)
+        // See: Johannsen & Psaltis (2010), ApJ 718, 446
+        let edge_on = black_hole(0.1).shadow(FRAC_PI_2).unwrap();
+        let inclined = black_hole(0.1).shadow(1.0).unwrap();
+
+        assert!((edge_on.displacement - 0.2).abs() < 1e-3, "{}", edge_on.displacement);
+        assert!((inclined.displacement - 0.2 * 1.0_f64.sin()).abs() < 1e-3, "{}", inclined.displacement);
+    }
+
+    #[test]
+    fn charged_black_holes_and_naked_singularities_have_no_shadow() {
+
+        let mass = 6.5e9 * SOLAR_MASS;
+        let charged = BlackHoleBuilder::new("Charged")
+            .mass(mass)
+            .electric_charge(1.0e20)
+            .build();
+
+        assert!(charged.charge_length_squared() > 0.0);
+        assert_eq!(None, charged.shadow(1.0));
+        assert_eq!(None, black_hole(1.1).shadow(1.0));
+        assert!(BlackHoleBuilder::new("Neutral").mass(mass).electric_charge(0.0).build().shadow(1.0).is_some());
+    }
+
+    #[test]
+    fn m87_shadow_in_microarcseconds() {
+
+        let m87 = black_hole(0.9);
+        let inclination = 17.0_f64.to_radians();
+        let shadow = m87.apparent_shadow(inclination, 16.8e6 * 3.085_677_581e16).unwrap();
+
+        assert!(shadow.diameter > 37.0 && shadow.diameter < 41.0, "{}", shadow.diameter);
+
+        let cosmology = Cosmology::default();
+        let distance = cosmology.angular_diameter_distance(0.004_283);
+        assert_eq!(m87.apparent_shadow(inclination, distance), m87.apparent_shadow_at_redshift(inclination, &cosmology));
+    }
+}
@@ -0,0 +1,204 @@
+
+use universe::{BlackHole, GRAVITATIONAL_CONSTANT, REDUCED_PLANCK_CONSTANT, SPEED_OF_LIGHT};
+
+/// Superradiance: a rotating black hole amplifies waves whose angular phase velocity is
+/// smaller than the rotation of its horizon. A massive boson (e.g. an axion) can be trapped in
+/// bound states around the black hole, so the amplification repeats, and a cloud of bosons
+/// grows exponentially by extracting angular momentum from the black hole. Observing rapidly
+/// rotating black holes therefore excludes ranges of boson masses.
+/// See: https://en.wikipedia.org/wiki/Superradiance
+///
+/// The boson mass is given in kg (1 eV/c^2 = 1.783e-36 kg), l and m are the orbital and
+/// azimuthal quantum numbers of the bound state. The growth rates hold for the states
+/// without radial nodes in the limit of small couplings (alpha << l). Only states with
+/// 0 < m <= l can be superradiant, the methods return None for other quantum numbers.
+/// All methods return None if the mass is unknown or the black hole has no horizon.
+impl BlackHole {
+
+    /// Calculates the gravitational fine structure constant, the ratio of the gravitational
+    /// radius to the reduced Compton wavelength of the boson
+    ///
+    ///         G * M * mu
+    /// alpha = ----------
+    ///          hbar * c
+    ///
+    pub fn superradiance_coupling(&self, boson_mass: f64) -> Option<f64> {
+        self.mass.map(|mass| GRAVITATIONAL_CONSTANT * mass * boson_mass
+            / (REDUCED_PLANCK_CONSTANT * SPEED_OF_LIGHT))
+    }
+
+    /// Checks the superradiance condition: the frequency of the bound state (which is close
+    /// to the rest mass of the boson) has to be smaller than m times the horizon angular velocity
+    ///
+    /// omega < m * OmegaH
+    ///
+    pub fn is_superradiant(&self, boson_mass: f64, m: u32) -> Option<bool> {
+        let frequency = boson_mass * SPEED_OF_LIGHT.powi(2) / REDUCED_PLANCK_CONSTANT;
+        Some(frequency < m as f64 * self.horizon_angular_velocity()?)
+    }
+
+    /// Calculates the growth rate of the boson cloud in 1/s (negative if the state is
+    /// absorbed by the black hole) using the result of Detweiler (1980), corrected by a
+    /// factor of 1/2 (Pani et al. 2012). For l = m = 1 it reduces to
+    /// Gamma = X * alpha^8 * mu * c^2 / (48 * hbar). With r+, OmegaH and alpha in units of
+    /// the gravitational radius, the rate is
+    ///
+    ///         mu * c^2                                                 l
+    /// Gamma = -------- * alpha^(4l+4) * r+ * (m * OmegaH - alpha) * Cl * Prod (j^2 * (1 - X^2) + (m * X - 2 * alpha * r+)^2)
+    ///           hbar                                                   j=1
+    ///
+    ///      2^(4l+2) * (2l+1)!   (      l!      )^2
+    /// Cl = ------------------ * (---------------)
+    ///        (l+1)^(2l+4)       ((2l)! * (2l+1)!)
+    ///
+    pub fn superradiant_rate(&self, boson_mass: f64, l: u32, m: u32) -> Option<f64> {
+        if m == 0 || m > l {
+            return None;
+        }
+
+        let rg = self.gravitational_radius()?;
+        let spin = self.dimensionless_spin()?;
+        let horizon = self.outer_horizon_radius()? / rg;
+        let angular_velocity = self.horizon_angular_velocity()? * rg / SPEED_OF_LIGHT;
+        let alpha = self.superradiance_coupling(boson_mass)?;
+        let (l, m) = (l as i32, m as f64);
+
+        let coefficient = 2.0_f64.powi(4 * l + 2) * factorial(2 * l + 1) / (l as f64 + 1.0).powi(2 * l + 4)
+            * (factorial(l) / (factorial(2 * l) * factorial(2 * l + 1))).powi(2);
+        let product: f64 = (1..=l)
+            .map(|j| (j as f64).powi(2) * (1.0 - spin.powi(2)) + (m * spin - 2.0 * alpha * horizon).powi(2))
+            .product();
+
+        Some(boson_mass * SPEED_OF_LIGHT.powi(2) / REDUCED_PLANCK_CONSTANT * alpha.powi(4 * l + 4)
+            * horizon * (m * angular_velocity - alpha) * coefficient * product)
+    }
+
+    /// Calculates the e-folding time of the boson cloud in seconds. Returns None if the
+    /// superradiance condition is not met.
+    pub fn superradiant_growth_time(&self, boson_mass: f64, l: u32, m: u32) -> Option<f64> {
+        let rate = self.superradiant_rate(boson_mass, l, m)?;
+
+        if rate <= 0.0 {
+            return None;
+        }
+        Some(1.0 / rate)
+    }
+
+    /// Calculates the dimensionless spin at which the growth of the cloud stops, because the
+    /// horizon has slowed down until m * OmegaH = omega. Black holes which are already
+    /// rotating slower keep their spin.
+    ///
+    ///        4 * m * alpha
+    /// Xf = ----------------
+    ///      m^2 + 4 * alpha^2
+    ///
+    pub fn superradiant_final_spin(&self, boson_mass: f64, m: u32) -> Option<f64> {
+        if m == 0 {
+            return None;
+        }
+
+        let spin = self.dimensionless_spin()?;
+        let alpha = self.superradiance_coupling(boson_mass)?;
+        let m = m as f64;
+
+        Some(spin.min(4.0 * m * alpha / (m.powi(2) + 4.0 * alpha.powi(2))))
+    }
+
+    /// Calculates the mass of the boson cloud in kg once it has spun down the black hole.
+    /// Every boson carries the energy hbar * omega and the angular momentum m * hbar, so
+    /// the cloud takes the mass omega / m per angular momentum extracted from the black hole.
+    ///
+    ///           alpha
+    /// Mcloud = ----- * (X - Xf) * M
+    ///             m
+    ///
+    pub fn superradiant_cloud_mass(&self, boson_mass: f64, m: u32) -> Option<f64> {
+        let spin = self.dimensionless_spin()?;
+        let final_spin = self.superradiant_final_spin(boson_mass, m)?;
+        self.outer_horizon_radius()?;
+
+        Some(self.superradiance_coupling(boson_mass)? / m as f64 * (spin - final_spin) * self.mass?)
+    }
+}
+
+fn factorial(n: i32) -> f64 {
+    (1..=n).map(|i| i as f64).product()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use universe::{BlackHole, BlackHoleBuilder, SOLAR_MASS, Type};
+    use universe::{GRAVITATIONAL_CONSTANT, REDUCED_PLANCK_CONSTANT, SPEED_OF_LIGHT};
+
+    fn stellar(spin: f64) -> BlackHole {
+        let mass = 10.0 * SOLAR_MASS;
+        BlackHoleBuilder::new("GRS 1915+105")
+            .mass(mass)
+            .angular_momentum(spin * GRAVITATIONAL_CONSTANT * mass.powi(2) / SPEED_OF_LIGHT)
+            .classification(Type::Stellar)
+            .build()
+    }
+
+    /// The boson mass in kg for the given coupling
+    fn boson_mass(black_hole: &BlackHole, alpha: f64) -> f64 {
+        alpha * REDUCED_PLANCK_CONSTANT * SPEED_OF_LIGHT / (GRAVITATIONAL_CONSTANT * black_hole.mass.unwrap())
+    }
+
+    #[test]
+    fn small_coupling_growth_rate() {
+
+        let black_hole = stellar(0.1);
+        let boson = boson_mass(&black_hole, 1e-3);
+        assert!((black_hole.superradiance_coupling(boson).unwrap() - 1e-3).abs() < 1e-15);
+        assert_eq!(Some(true), black_hole.is_superradiant(boson, 1));
+
+        let rate = black_hole.superradiant_rate(boson, 1, 1).unwrap();
+        let expected = 0.1 * 1e-3_f64.powi(8) * boson * SPEED_OF_LIGHT.powi(2) / (48.0 * REDUCED_PLANCK_CONSTANT);
+        assert!((rate / expected - 1.0).abs() < 0.1, "{} != {}", rate, expected);
+        assert_eq!(Some(1.0 / rate), black_hole.superradiant_growth_time(boson, 1, 1));
+    }
+
+    #[test]
+    fn cloud_spins_down_black_hole_until_condition_is_saturated() {
+
+        let black_hole = stellar(0.9);
+        let boson = boson_mass(&black_hole, 0.1);
+        let final_spin = black_hole.superradiant_final_spin(boson, 1).unwrap();
+        assert!(final_spin < 0.9);
+
+        let spun_down = stellar(final_spin);
+        let frequency = boson * SPEED_OF_LIGHT.powi(2) / REDUCED_PLANCK_CONSTANT;
+        assert!((spun_down.horizon_angular_velocity().unwrap() / frequency - 1.0).abs() < 1e-9);
+
+        let cloud = black_hole.superradiant_cloud_mass(boson, 1).unwrap();
+        assert!((cloud / black_hole.mass.unwrap() - 0.1 * (0.9 - final_spin)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn heavy_bosons_are_absorbed() {
+
+        let black_hole = stellar(0.5);
+        let boson = boson_mass(&black_hole, 0.6);
+
+        assert_eq!(Some(false), black_hole.is_superradiant(boson, 1));
+        assert!(black_hole.superradiant_rate(boson, 1, 1).unwrap() < 0.0);
+        assert_eq!(None, black_hole.superradiant_growth_time(boson, 1, 1));
+        assert_eq!(Some(0.5), black_hole.superradiant_final_spin(boson, 1));
+        assert_eq!(Some(0.0), black_hole.superradiant_cloud_mass(boson, 1));
+    }
+
+    #[test]
+    fn invalid_quantum_numbers() {
+
+        let black_hole = stellar(0.9);
+        let boson = boson_mass(&black_hole, 0.1);
+
+        assert_eq!(None, black_hole.superradiant_rate(boson, 1, 0));
+        assert_eq!(None, black_hole.superradiant_rate(boson, 1, 2));
+        assert_eq!(None, black_hole.superradiant_growth_time(boson, 2, 3));
+        assert_eq!(None, black_hole.superradiant_final_spin(boson, 0));
+        assert_eq!(None, black_hole.superradiant_cloud_mass(boson, 0));
+        assert!(black_hole.superradiant_rate(boson, 2, 2).unwrap() > 0.0);
+    }
+}
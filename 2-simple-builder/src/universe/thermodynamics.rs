@@ -0,0 +1,121 @@
+
+use std::f64::consts::{LN_2, PI};
+
+use universe::{BlackHole, BOLTZMANN_CONSTANT, GRAVITATIONAL_CONSTANT, REDUCED_PLANCK_CONSTANT,
+               SPEED_OF_LIGHT, STEFAN_BOLTZMANN_CONSTANT};
+
+/// Black hole thermodynamics: a black hole emits Hawking radiation like a black body
+/// whose temperature is set by the surface gravity of its event horizon, and it
+/// carries an entropy proportional to the area of the horizon.
+///
+/// All methods return None if the mass is unknown or if the parameters describe
+/// a naked singularity.
+impl BlackHole {
+
+    /// Calculates the Hawking temperature in Kelvin. Rotation and charge lower the
+    /// temperature, an extremal black hole does not radiate at all.
+    /// See: https://en.wikipedia.org/wiki/Hawking_radiation
+    ///
+    ///       hbar * kappa
+    /// T = ----------------
+    ///     2 * pi * c * kB
+    ///
+    pub fn hawking_temperature(&self) -> Option<f64> {
+        Some(REDUCED_PLANCK_CONSTANT * self.surface_gravity()?
+            / (2.0 * PI * SPEED_OF_LIGHT * BOLTZMANN_CONSTANT))
+    }
+
+    /// Calculates the Bekenstein-Hawking entropy in J/K.
+    /// See: https://en.wikipedia.org/wiki/Black_hole_thermodynamics
+    ///
+    ///     kB * c^3 * A
+    /// S = ------------
+    ///     4 * G * hbar
+    ///
+    pub fn entropy(&self) -> Option<f64> {
+        Some(BOLTZMANN_CONSTANT * SPEED_OF_LIGHT.powi(3) * self.horizon_area()?
+            / (4.0 * GRAVITATIONAL_CONSTANT * REDUCED_PLANCK_CONSTANT))
+    }
+
+    /// Calculates the Bekenstein-Hawking entropy in bits
+    pub fn entropy_bits(&self) -> Option<f64> {
+        Some(self.entropy()? / (BOLTZMANN_CONSTANT * LN_2))
+    }
+
+    /// Calculates the power radiated by the black hole in Watts. The horizon is
+    /// treated as a perfect black body emitting photons only (greybody factors
+    /// are neglected). This textbook estimate underestimates the actual power
+    /// by a factor of about 13, see `mass_loss_rate` for the full emission.
+    ///
+    /// P = sigma * A * T^4
+    ///
+    pub fn hawking_power(&self) -> Option<f64> {
+        Some(STEFAN_BOLTZMANN_CONSTANT * self.horizon_area()? * self.hawking_temperature()?.powi(4))
+    }
+
+    /// Calculates the time in seconds until the black hole has evaporated completely.
+    ///
+    /// A rotating or charged black hole sheds its angular momentum and charge long
+    /// before it has lost a significant fraction of its mass, so the lifetime is
+    /// dominated by the Schwarzschild phase and only depends on the mass. Like
+    /// `hawking_power` this is the photon-only estimate, which overestimates the
+    /// lifetime by a factor of about 13 (see `EvaporationSimulation`).
+    ///
+    ///     5120 * pi * G^2 * M^3
+    /// t = ---------------------
+    ///          hbar * c^4
+    ///
+    pub fn evaporation_time(&self) -> Option<f64> {
+        self.outer_horizon_radius()?;
+
+        self.mass.map(|mass| 5120.0 * PI * GRAVITATIONAL_CONSTANT.powi(2) * mass.powi(3)
+            / (REDUCED_PLANCK_CONSTANT * SPEED_OF_LIGHT.powi(4)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use universe::{BlackHoleBuilder, SOLAR_MASS, Type};
+
+    fn assert_relative(expected: f64, actual: Option<f64>) {
+        let actual = actual.unwrap();
+        assert!(((actual - expected) / expected).abs() < 1e-3, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn solar_mass_black_hole() {
+
+        let black_hole = BlackHoleBuilder::new("Cygnus X-1")
+            .mass(SOLAR_MASS)
+            .classification(Type::Stellar)
+            .build();
+
+        // published values for one solar mass: 6.17e-8 K, 9e-29 W and a photon-only
+        // lifetime of 2.1e67 years, see https://en.wikipedia.org/wiki/Hawking_radiation
+        assert_relative(6.17e-8, black_hole.hawking_temperature());
+        assert_relative(1.449e54, black_hole.entropy());
+        assert_relative(1.514e77, black_hole.entropy_bits());
+        assert_relative(9.00e-29, black_hole.hawking_power());
+        assert_relative(6.619e74, black_hole.evaporation_time());
+    }
+
+    #[test]
+    fn rotating_and_charged_micro_black_holes_are_colder() {
+
+        let schwarzschild = BlackHoleBuilder::new("Micro")
+            .mass(1.0e12)
+            .classification(Type::Micro)
+            .build();
+        let kerr_newman = BlackHoleBuilder::new("Micro")
+            .mass(1.0e12)
+            .angular_momentum(1.0e5)
+            .electric_charge(50.0)
+            .classification(Type::Micro)
+            .build();
+
+        assert!(kerr_newman.hawking_temperature() < schwarzschild.hawking_temperature());
+        assert!(kerr_newman.entropy() < schwarzschild.entropy());
+        assert_eq!(schwarzschild.evaporation_time(), kerr_newman.evaporation_time());
+    }
+}
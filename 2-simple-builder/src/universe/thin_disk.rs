@@ -0,0 +1,224 @@
+
+use std::f64::consts::PI;
+use std::io::{self, Write};
+
+use universe::{BlackHole, BOLTZMANN_CONSTANT, GRAVITATIONAL_CONSTANT, REDUCED_PLANCK_CONSTANT,
+               SPEED_OF_LIGHT, STEFAN_BOLTZMANN_CONSTANT, simpson};
+use universe::circular_orbit::OrbitDirection;
+
+const DEFAULT_OUTER_RADIUS: f64 = 1.0e5; // gravitational radii
+const SPECTRUM_INTEGRATION_INTERVALS: usize = 512;
+
+/// The temperature of the disk in Kelvin at radii in meters
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemperatureProfile {
+    pub points: Vec<(f64, f64)>
+}
+
+impl TemperatureProfile {
+
+    /// Writes the profile as CSV with the columns radius and temperature
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_csv(writer, "radius_m,temperature_K", &self.points)
+    }
+}
+
+/// The spectral luminosity of the disk in W/Hz at frequencies in Hz
+#[derive(Clone, Debug, PartialEq)]
+pub struct Spectrum {
+    pub points: Vec<(f64, f64)>
+}
+
+impl Spectrum {
+
+    /// Writes the spectrum as CSV with the columns frequency and spectral luminosity
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_csv(writer, "frequency_Hz,luminosity_W_per_Hz", &self.points)
+    }
+}
+
+/// A geometrically thin, optically thick accretion disk (Shakura-Sunyaev) around a black
+/// hole. Every ring of the disk radiates like a black body with its local temperature, so
+/// the spectrum of the whole disk is a multicolor black body.
+/// See: https://en.wikipedia.org/wiki/Accretion_disk
+///
+/// Radii are measured in meters, the accretion rate in kg/s. Relativistic corrections
+/// to the temperature profile are neglected.
+///
+/// # Examples
+///
+/// ```
+/// let disk = ThinDisk::new(&cygnus_x1, 1.0e15)
+///     .unwrap()
+///     .outer_radius(1.0e9);
+///
+/// disk.spectrum(&frequencies).write_csv(&mut file).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThinDisk {
+    mass: f64,
+    accretion_rate: f64,
+    inner_radius: f64,
+    isco_radius: f64,
+    outer_radius: f64
+}
+
+impl ThinDisk {
+
+    /// Constructs a disk with the given accretion rate, which reaches down to the innermost
+    /// stable circular orbit. Returns None if the mass is unknown or the black hole has no
+    /// such orbit.
+    pub fn new(black_hole: &BlackHole, accretion_rate: f64) -> Option<ThinDisk> {
+        let isco = black_hole.isco_radius(OrbitDirection::Prograde)?;
+
+        Some(ThinDisk {
+            mass:           black_hole.mass?,
+            accretion_rate,
+            inner_radius:   isco,
+            isco_radius:    isco,
+            outer_radius:   DEFAULT_OUTER_RADIUS * black_hole.gravitational_radius()?
+        })
+    }
+
+    /// Sets the inner radius of the disk (None resets it to the innermost stable circular orbit)
+    pub fn inner_radius<I>(mut self, inner_radius: I) -> ThinDisk
+        where I: Into<Option<f64>> {
+
+        self.inner_radius = inner_radius.into().unwrap_or(self.isco_radius);
+        self
+    }
+
+    /// Sets the outer radius of the disk
+    pub fn outer_radius(mut self, outer_radius: f64) -> ThinDisk {
+        self.outer_radius = outer_radius;
+        self
+    }
+
+    /// Calculates the effective temperature in Kelvin at the given radius
+    ///
+    ///     ( 3 * G * M * Mdot                     )^(1/4)
+    /// T = ( ---------------- * (1 - sqrt(rin / r)) )
+    ///     ( 8 * pi * sigma * r^3                 )
+    ///
+    pub fn temperature(&self, radius: f64) -> f64 {
+        if radius < self.inner_radius {
+            return 0.0;
+        }
+        (3.0 * GRAVITATIONAL_CONSTANT * self.mass * self.accretion_rate
+            / (8.0 * PI * STEFAN_BOLTZMANN_CONSTANT * radius.powi(3))
+            * (1.0 - (self.inner_radius / radius).sqrt())).powf(0.25)
+    }
+
+    /// Calculates the temperature at the given number of logarithmically spaced radii
+    /// between the inner and the outer radius
+    pub fn temperature_profile(&self, points: usize) -> TemperatureProfile {
+        let ratio = (self.outer_radius / self.inner_radius).ln();
+        let steps = points.max(2) - 1;
+
+        TemperatureProfile {
+            points: (0..=steps)
+                .map(|i| self.inner_radius * (ratio * i as f64 / steps as f64).exp())
+                .map(|radius| (radius, self.temperature(radius)))
+                .collect()
+        }
+    }
+
+    /// Calculates the spectral luminosity in W/Hz at the given frequency, emitted by both
+    /// faces of the disk
+    ///
+    /// Lnu = 4 * pi^2 * integral(r * Bnu(T(r)) dr)
+    ///
+    pub fn spectral_luminosity(&self, frequency: f64) -> f64 {
+        let planck = |log_radius: f64| {
+            let radius = log_radius.exp();
+            radius.powi(2) * planck_function(frequency, self.temperature(radius))
+        };
+
+        4.0 * PI.powi(2) * simpson(planck, self.inner_radius.ln(), self.outer_radius.ln(),
+                                   SPECTRUM_INTEGRATION_INTERVALS)
+    }
+
+    /// Calculates the spectral energy distribution over the given frequency grid
+    pub fn spectrum(&self, frequencies: &[f64]) -> Spectrum {
+        Spectrum {
+            points: frequencies.iter()
+                .map(|&frequency| (frequency, self.spectral_luminosity(frequency)))
+                .collect()
+        }
+    }
+}
+
+/// The spectral radiance of a black body in W/(m^2*Hz*sr)
+///
+///          2 * h * nu^3            1
+/// Bnu = ------------- * -------------------
+///            c^2        exp(h * nu / kT) - 1
+///
+fn planck_function(frequency: f64, temperature: f64) -> f64 {
+    if temperature <= 0.0 {
+        return 0.0;
+    }
+    let planck_constant = 2.0 * PI * REDUCED_PLANCK_CONSTANT;
+
+    2.0 * planck_constant * frequency.powi(3) / SPEED_OF_LIGHT.powi(2)
+        / (planck_constant * frequency / (BOLTZMANN_CONSTANT * temperature)).exp_m1()
+}
+
+fn write_csv<W: Write>(writer: &mut W, header: &str, points: &[(f64, f64)]) -> io::Result<()> {
+    writeln!(writer, "{}", header)?;
+    for &(x, y) in points {
+        writeln!(writer, "{:e},{:e}", x, y)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, Type};
+
+    fn x_ray_binary() -> ThinDisk {
+        let black_hole = BlackHoleBuilder::new("Cygnus X-1")
+            .mass(10.0 * SOLAR_MASS)
+            .classification(Type::Stellar)
+            .build();
+        ThinDisk::new(&black_hole, 1.0e15).unwrap()
+    }
+
+    #[test]
+    fn temperature_profile_peaks_close_to_inner_edge() {
+
+        let disk = x_ray_binary();
+        let profile = disk.temperature_profile(200);
+        let &(peak_radius, peak_temperature) = profile.points.iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+
+        assert_eq!(0.0, profile.points[0].1);
+        assert!((peak_radius / disk.inner_radius - 49.0 / 36.0).abs() < 0.05);
+        assert!(peak_temperature > 1.0e6 && peak_temperature < 1.0e8);
+
+        let mut csv = Vec::new();
+        profile.write_csv(&mut csv).unwrap();
+        assert!(String::from_utf8(csv).unwrap().starts_with("radius_m,temperature_K\n"));
+    }
+
+    #[test]
+    fn spectrum_integrates_to_disk_luminosity() {
+
+        let disk = x_ray_binary();
+        let luminosity = simpson(|log_frequency: f64| {
+            let frequency = log_frequency.exp();
+            frequency * disk.spectral_luminosity(frequency)
+        }, 10.0_f64.ln() * 10.0, 10.0_f64.ln() * 20.0, 400);
+
+        // twice the dissipated power per face, integrated from the inner to the outer edge
+        let x = disk.inner_radius / disk.outer_radius;
+        let expected = GRAVITATIONAL_CONSTANT * disk.mass * disk.accretion_rate / (2.0 * disk.inner_radius)
+            * (1.0 - 3.0 * x + 2.0 * x.powf(1.5));
+
+        assert!((luminosity / expected - 1.0).abs() < 1e-4, "{} != {}", luminosity, expected);
+        assert_eq!(vec![(1.0e17, disk.spectral_luminosity(1.0e17))], disk.spectrum(&[1.0e17]).points);
+    }
+}
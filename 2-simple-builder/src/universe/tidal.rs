@@ -0,0 +1,136 @@
+
+use universe::{BlackHole, GRAVITATIONAL_CONSTANT};
+
+/// The radius at which an infalling body gets torn apart by the tidal forces
+/// of the black hole (spaghettification)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Spaghettification {
+
+    /// The body is torn apart before it reaches the event horizon (typical for
+    /// stellar black holes)
+    OutsideHorizon(f64),
+
+    /// The body crosses the event horizon in one piece (typical for supermassive
+    /// black holes)
+    InsideHorizon(f64)
+}
+
+/// Tidal forces acting on a body falling radially into the black hole. The tidal
+/// acceleration is calculated in the Newtonian limit, which is a good approximation
+/// outside of a few gravitational radii.
+///
+/// All methods return None if the mass is unknown or if a length, distance or
+/// tolerance is not positive.
+impl BlackHole {
+
+    /// Calculates the difference in acceleration in m/s^2 between both ends of a body
+    /// with the given length (in meters) at the given radial distance (in meters)
+    /// See: https://en.wikipedia.org/wiki/Spaghettification
+    ///
+    ///      2 * G * M * l
+    /// da = -------------
+    ///           r^3
+    ///
+    pub fn tidal_acceleration(&self, length: f64, distance: f64) -> Option<f64> {
+        if length <= 0.0 || distance <= 0.0 {
+            return None;
+        }
+        self.mass.map(|mass| 2.0 * GRAVITATIONAL_CONSTANT * mass * length / distance.powi(3))
+    }
+
+    /// Calculates the radius (in meters) at which the tidal acceleration acting on a body
+    /// with the given length exceeds the given tolerance (in m/s^2)
+    ///
+    ///     (2 * G * M * l)^(1/3)
+    /// r = (-------------)
+    ///     (  tolerance  )
+    ///
+    pub fn tidal_disruption_radius(&self, length: f64, tolerance: f64) -> Option<f64> {
+        if length <= 0.0 || tolerance <= 0.0 {
+            return None;
+        }
+        self.mass.map(|mass| (2.0 * GRAVITATIONAL_CONSTANT * mass * length / tolerance).cbrt())
+    }
+
+    /// Determines where a body with the given length is torn apart, and whether that
+    /// happens inside or outside of the event horizon. Returns None if the parameters
+    /// describe a naked singularity.
+    pub fn spaghettification(&self, length: f64, tolerance: f64) -> Option<Spaghettification> {
+        let horizon = self.outer_horizon_radius()?;
+        let radius = self.tidal_disruption_radius(length, tolerance)?;
+
+        if radius > horizon {
+            Some(Spaghettification::OutsideHorizon(radius))
+        } else {
+            Some(Spaghettification::InsideHorizon(radius))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, SOLAR_MASS, Type};
+    const HUMAN_HEIGHT: f64 = 2.0;
+    const TOLERANCE: f64 = 100.0 * 9.81;
+
+    #[test]
+    fn tidal_acceleration_matches_disruption_radius() {
+
+        let black_hole = BlackHoleBuilder::new("Cygnus X-1").mass(15.0 * SOLAR_MASS).build();
+        let radius = black_hole.tidal_disruption_radius(HUMAN_HEIGHT, TOLERANCE).unwrap();
+        let acceleration = black_hole.tidal_acceleration(HUMAN_HEIGHT, radius).unwrap();
+
+        assert!((acceleration - TOLERANCE).abs() < 1e-9 * TOLERANCE);
+        assert!(black_hole.tidal_acceleration(HUMAN_HEIGHT, 2.0 * radius).unwrap() < TOLERANCE);
+    }
+
+    #[test]
+    fn unphysical_parameters_are_rejected() {
+
+        let black_hole = BlackHoleBuilder::new("Cygnus X-1").mass(15.0 * SOLAR_MASS).build();
+
+        assert_eq!(None, black_hole.tidal_acceleration(0.0, 1.0e6));
+        assert_eq!(None, black_hole.tidal_acceleration(-HUMAN_HEIGHT, 1.0e6));
+        assert_eq!(None, black_hole.tidal_acceleration(HUMAN_HEIGHT, 0.0));
+        assert_eq!(None, black_hole.tidal_disruption_radius(0.0, TOLERANCE));
+        assert_eq!(None, black_hole.tidal_disruption_radius(HUMAN_HEIGHT, 0.0));
+        assert_eq!(None, black_hole.tidal_disruption_radius(HUMAN_HEIGHT, -TOLERANCE));
+        assert_eq!(None, black_hole.spaghettification(-HUMAN_HEIGHT, TOLERANCE));
+        assert_eq!(None, black_hole.spaghettification(HUMAN_HEIGHT, 0.0));
+    }
+
+    #[test]
+    fn lunar_tide_on_earth() {
+
+        // the Moon pulls the near side of the Earth with 1.1e-7 g more than its center
+        // See: https://en.wikipedia.org/wiki/Tidal_force
+        let moon = BlackHoleBuilder::new("Moon").mass(7.342e22).build();
+        let acceleration = moon.tidal_acceleration(6.371e6, 3.844e8).unwrap();
+
+        assert!((acceleration / 9.81 - 1.1e-7).abs() < 0.05e-7, "{}", acceleration);
+    }
+
+    #[test]
+    fn stellar_and_supermassive_black_holes_differ() {
+
+        let stellar = BlackHoleBuilder::new("Cygnus X-1")
+            .mass(15.0 * SOLAR_MASS)
+            .classification(Type::Stellar)
+            .build();
+        let supermassive = BlackHoleBuilder::new("Sagittarius A*")
+            .mass(4.1e6 * SOLAR_MASS)
+            .classification(Type::SuperMassive)
+            .build();
+
+        match stellar.spaghettification(HUMAN_HEIGHT, TOLERANCE) {
+            Some(Spaghettification::OutsideHorizon(_)) => (),
+            other => panic!("unexpected {:?}", other)
+        }
+        match supermassive.spaghettification(HUMAN_HEIGHT, TOLERANCE) {
+            Some(Spaghettification::InsideHorizon(_)) => (),
+            other => panic!("unexpected {:?}", other)
+        }
+    }
+}
@@ -0,0 +1,152 @@
+
+use universe::BlackHole;
+use universe::circular_orbit::OrbitDirection;
+
+/// Gravitational time dilation as seen by a distant observer. A time dilation
+/// factor of 0.5 means that a clock close to the black hole ticks at half the rate
+/// of a clock far away; one hour close to the black hole then corresponds to two
+/// hours far away.
+///
+/// All observers are located in the equatorial plane. The methods return None if
+/// the mass is unknown or if no such observer can exist at the given radius.
+impl BlackHole {
+
+    /// Calculates the time dilation factor (dtau/dt) for an observer who hovers at the
+    /// given radius (in meters). There are no static observers inside the ergosphere,
+    /// and none at or inside the event horizon, which includes the static region inside
+    /// the inner horizon of a charged black hole.
+    /// See: https://en.wikipedia.org/wiki/Gravitational_time_dilation
+    ///
+    ///                  2 * rg * r - rQ^2
+    /// dtau/dt = sqrt(1 - -----------------)
+    ///                         r^2
+    ///
+    pub fn static_time_dilation(&self, radius: f64) -> Option<f64> {
+        let rg = self.gravitational_radius()?;
+        let lapse = 1.0 - (2.0 * rg * radius - self.charge_length_squared()) / radius.powi(2);
+
+        if radius <= self.outer_horizon_radius().unwrap_or(0.0) || lapse <= 0.0 {
+            return None;
+        }
+        Some(lapse.sqrt())
+    }
+
+    /// Calculates the gravitational redshift z of light emitted by a static observer at
+    /// the given radius and received far away
+    ///
+    ///       1
+    /// z = ------- - 1
+    ///     dtau/dt
+    ///
+    pub fn static_redshift(&self, radius: f64) -> Option<f64> {
+        self.static_time_dilation(radius).map(|factor| 1.0 / factor - 1.0)
+    }
+
+    /// Calculates the time dilation factor (dtau/dt) for an observer on a circular orbit
+    /// with the given radius (in meters). It combines the gravitational time dilation with
+    /// the one caused by the orbital motion, including frame dragging of a rotating black
+    /// hole. The electric charge is not taken into account. There are no circular orbits
+    /// inside the photon orbit.
+    /// See: Bardeen, Press & Teukolsky (1972), ApJ 178, 347
+    ///
+    ///           sqrt(1 - 3 * rg / r +/- 2 * X * (rg / r)^(3/2))
+    /// dtau/dt = -----------------------------------------------
+    ///                     1 +/- X * (rg / r)^(3/2)
+    ///
+    pub fn orbital_time_dilation(&self, radius: f64, direction: OrbitDirection) -> Option<f64> {
+        let rg = self.gravitational_radius()?;
+        let spin = self.dimensionless_spin()?.abs();
+        let frame_dragging = direction.sign() * spin * (rg / radius).powf(1.5);
+        let numerator = 1.0 - 3.0 * rg / radius + 2.0 * frame_dragging;
+
+        if spin > 1.0 || numerator <= 0.0 {
+            return None;
+        }
+        Some(numerator.sqrt() / (1.0 + frame_dragging))
+    }
+
+    /// Calculates the redshift z of light emitted by an observer on a circular orbit with
+    /// the given radius, averaged over one orbit (i.e. without the Doppler shift along the
+    /// line of sight)
+    pub fn orbital_redshift(&self, radius: f64, direction: OrbitDirection) -> Option<f64> {
+        self.orbital_time_dilation(radius, direction).map(|factor| 1.0 / factor - 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::f64::consts::PI;
+
+    use super::*;
+    use universe::{BlackHoleBuilder, GRAVITATIONAL_CONSTANT, SOLAR_MASS, SPEED_OF_LIGHT,
+                   Type, VACUUM_PERMITTIVITY};
+
+    const MASS: f64 = 1.0e8 * SOLAR_MASS;
+
+    fn gargantua(spin: f64) -> BlackHole {
+        let rg = BlackHoleBuilder::new("Gargantua").mass(MASS).build().gravitational_radius().unwrap();
+
+        BlackHoleBuilder::new("Gargantua")
+            .discovered_by("Dr. Mann".to_string())
+            .mass(MASS)
+            .angular_momentum(spin * rg * MASS * SPEED_OF_LIGHT)
+            .classification(Type::SuperMassive)
+            .build()
+    }
+
+    #[test]
+    fn schwarzschild_time_dilation() {
+
+        let black_hole = gargantua(0.0);
+        let rg = black_hole.gravitational_radius().unwrap();
+
+        assert!((black_hole.static_time_dilation(8.0 * rg).unwrap() - 0.75_f64.sqrt()).abs() < 1e-12);
+        assert!((black_hole.static_redshift(8.0 * rg).unwrap() - (1.0 / 0.75_f64.sqrt() - 1.0)).abs() < 1e-12);
+        assert!((black_hole.orbital_time_dilation(6.0 * rg, OrbitDirection::Prograde).unwrap() - 0.5_f64.sqrt()).abs() < 1e-12);
+        assert_eq!(None, black_hole.static_time_dilation(1.5 * rg));
+        assert_eq!(None, black_hole.orbital_time_dilation(3.0 * rg, OrbitDirection::Retrograde));
+    }
+
+    #[test]
+    fn no_static_observers_inside_the_horizon() {
+
+        let black_hole = gargantua(0.0);
+        let rg = black_hole.gravitational_radius().unwrap();
+
+        assert_eq!(None, black_hole.static_time_dilation(0.0));
+        assert_eq!(None, black_hole.static_time_dilation(2.0 * rg));
+        assert_eq!(None, black_hole.static_redshift(rg));
+
+        // the metric is static again inside the inner horizon of a charged black hole
+        let charge = 0.8 * rg * SPEED_OF_LIGHT.powi(2) * (4.0 * PI * VACUUM_PERMITTIVITY / GRAVITATIONAL_CONSTANT).sqrt();
+        let charged = BlackHoleBuilder::new("Charged").mass(MASS).electric_charge(charge).build();
+
+        assert_eq!(None, charged.static_time_dilation(0.5 * charged.inner_horizon_radius().unwrap()));
+        assert!(charged.static_time_dilation(1.01 * charged.outer_horizon_radius().unwrap()).is_some());
+    }
+
+    #[test]
+    fn solar_gravitational_redshift() {
+
+        // the predicted redshift of light leaving the surface of the Sun corresponds to 636 m/s
+        let sun = BlackHoleBuilder::new("Sun").mass(SOLAR_MASS).build();
+        let velocity = sun.static_redshift(6.957e8).unwrap() * SPEED_OF_LIGHT;
+
+        assert!((velocity - 636.0).abs() < 1.0, "{}", velocity);
+    }
+
+    #[test]
+    fn millers_planet_time_dilation() {
+
+        // one hour on Miller's planet corresponds to seven years far away
+        let black_hole = gargantua(1.0 - 1e-14);
+        let isco = black_hole.isco_radius(OrbitDirection::Prograde).unwrap();
+        let factor = black_hole.orbital_time_dilation(1.000_001 * isco, OrbitDirection::Prograde).unwrap();
+        let hours_far_away = 1.0 / factor;
+
+        assert!(hours_far_away > 7.0 * 365.25 * 24.0);
+        assert!(black_hole.static_time_dilation(1.5 * black_hole.gravitational_radius().unwrap()).is_none());
+        assert!(black_hole.orbital_time_dilation(isco, OrbitDirection::Retrograde).is_none());
+    }
+}
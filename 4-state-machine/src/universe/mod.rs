@@ -1,6 +1,8 @@
 
 extern crate test;
 
+pub mod orbits;
+
 use std::f64;
 use std::convert::From;
 
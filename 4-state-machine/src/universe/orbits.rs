@@ -0,0 +1,176 @@
+
+use std::f64::consts::PI;
+
+use universe::BlackHole;
+
+const GRAVITATIONAL_CONSTANT: f64 = 6.674_30e-11; // N*m^2/kg^2
+const SPEED_OF_LIGHT: f64 = 299_792_458.0; // m/s
+
+/// The orbit of a test particle (e.g. a star or a planet) around a black hole.
+/// The relativistic effects are calculated to first post-Newtonian order, so the
+/// orbit should be well outside of the innermost stable circular orbit.
+///
+/// Only the mass and angular momentum of the black hole are used. A black hole
+/// without angular momentum is treated as non-rotating. All methods return None
+/// if the mass of the black hole is unknown. The formulas themselves are also
+/// available as free functions of the mass and angular momentum.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Orbit {
+
+    /// The semi-major axis in meters
+    pub semi_major_axis: f64,
+
+    /// The eccentricity (0 for circular orbits)
+    pub eccentricity: f64,
+
+    /// The inclination in radians relative to the equatorial plane of the black hole
+    pub inclination: f64
+}
+
+impl Orbit {
+
+    /// Constructs a new orbit
+    pub fn new(semi_major_axis: f64, eccentricity: f64, inclination: f64) -> Orbit {
+        Orbit {
+            semi_major_axis,
+            eccentricity,
+            inclination
+        }
+    }
+
+    /// Calculates the orbital period in seconds as measured by a distant observer
+    pub fn period(&self, black_hole: &BlackHole) -> Option<f64> {
+        black_hole.mass.map(|mass| orbital_period(mass, self.semi_major_axis))
+    }
+
+    /// Calculates the advance of the periapsis in radians per orbit. It consists of the
+    /// Schwarzschild precession and the Lense-Thirring precession caused by frame dragging,
+    /// which is three times the nodal precession for an equatorial orbit.
+    /// See: https://en.wikipedia.org/wiki/Apsidal_precession
+    ///
+    /// dw = dw_schwarzschild - 3 * cos(i) * dO
+    ///
+    pub fn periapsis_precession(&self, black_hole: &BlackHole) -> Option<f64> {
+        let schwarzschild = schwarzschild_precession(black_hole.mass?, self.semi_major_axis, self.eccentricity);
+        Some(schwarzschild - 3.0 * self.inclination.cos() * self.nodal_precession(black_hole)?)
+    }
+
+    /// Calculates the Lense-Thirring precession of the ascending node in radians per orbit
+    pub fn nodal_precession(&self, black_hole: &BlackHole) -> Option<f64> {
+        let angular_momentum = black_hole.angular_momentum.unwrap_or(0.0);
+        let rate = lense_thirring_rate(angular_momentum, self.semi_major_axis, self.eccentricity);
+
+        self.period(black_hole).map(|period| rate * period)
+    }
+}
+
+/// Calculates the Keplerian orbital period in seconds
+/// See: https://en.wikipedia.org/wiki/Orbital_period
+///
+///                   a^3
+/// P = 2 * pi * sqrt(-----)
+///                   G * M
+///
+pub fn orbital_period(mass: f64, semi_major_axis: f64) -> f64 {
+    2.0 * PI * (semi_major_axis.powi(3) / (GRAVITATIONAL_CONSTANT * mass)).sqrt()
+}
+
+/// Calculates the Schwarzschild (first post-Newtonian) advance of the periapsis in
+/// radians per orbit
+/// See: https://en.wikipedia.org/wiki/Apsidal_precession
+///
+///           6 * pi * G * M
+/// dw = -------------------
+///      c^2 * a * (1 - e^2)
+///
+pub fn schwarzschild_precession(mass: f64, semi_major_axis: f64, eccentricity: f64) -> f64 {
+    6.0 * PI * GRAVITATIONAL_CONSTANT * mass
+        / (SPEED_OF_LIGHT.powi(2) * semi_major_axis * (1.0 - eccentricity.powi(2)))
+}
+
+/// Calculates the Lense-Thirring precession rate of the ascending node in rad/s
+/// caused by a body with the given angular momentum
+/// See: https://en.wikipedia.org/wiki/Lense%E2%80%93Thirring_precession
+///
+///                 2 * G * J
+/// dO/dt = ---------------------------
+///         c^2 * a^3 * (1 - e^2)^(3/2)
+///
+pub fn lense_thirring_rate(angular_momentum: f64, semi_major_axis: f64, eccentricity: f64) -> f64 {
+    2.0 * GRAVITATIONAL_CONSTANT * angular_momentum
+        / (SPEED_OF_LIGHT.powi(2) * semi_major_axis.powi(3) * (1.0 - eccentricity.powi(2)).powf(1.5))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use universe::{BlackHoleBuilder, OptionalParamsBuilder};
+
+    const SOLAR_MASS: f64 = 1.988_47e30; // kg
+    const ARCSECONDS_PER_RADIAN: f64 = 206_264.806;
+    const JULIAN_YEAR: f64 = 365.25 * 86_400.0;
+    const EARTH_MASS: f64 = 5.972_2e24; // kg
+    const EARTH_ANGULAR_MOMENTUM: f64 = 5.86e33; // kg*m^2/s
+
+    fn body(name: &str) -> BlackHoleBuilder<OptionalParamsBuilder> {
+        BlackHole::new()
+            .name(name)
+            .discovered_by("Unknown")
+            .year_of_discovery(2017)
+    }
+
+    #[test]
+    fn perihelion_precession_of_mercury() {
+
+        // the famous 43 arcseconds per century
+        let sun = body("Sun").mass(SOLAR_MASS).build();
+        let mercury = Orbit::new(5.790_9e10, 0.205_6, 0.0);
+        let period = mercury.period(&sun).unwrap();
+        let precession = mercury.periapsis_precession(&sun).unwrap() * 100.0 * JULIAN_YEAR / period;
+
+        assert!((period / 86_400.0 - 87.97).abs() < 0.1);
+        assert!((precession * ARCSECONDS_PER_RADIAN - 43.0).abs() < 0.1);
+        assert_eq!(Some(0.0), mercury.nodal_precession(&sun));
+
+        let unknown = body("Unknown").angular_momentum(EARTH_ANGULAR_MOMENTUM).build();
+        assert_eq!(None, mercury.period(&unknown));
+        assert_eq!(None, mercury.periapsis_precession(&unknown));
+        assert_eq!(None, mercury.nodal_precession(&unknown));
+    }
+
+    #[test]
+    fn lense_thirring_precession_of_lageos() {
+
+        // the node of the LAGEOS satellite is dragged by the rotation of the Earth
+        // by 31 milliarcseconds per year
+        // See: Ciufolini & Pavlis (2004), Nature 431, 958
+        let rate = lense_thirring_rate(EARTH_ANGULAR_MOMENTUM, 1.227e7, 0.004_5);
+
+        assert!((rate * JULIAN_YEAR * ARCSECONDS_PER_RADIAN * 1e3 - 31.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn relativistic_precession_of_lageos_2() {
+
+        // the perigee of LAGEOS II advances by 3348 milliarcseconds per year, frame dragging
+        // moves its node by 31.5 and its perigee by -57 milliarcseconds per year
+        // See: Lucchesi & Peron (2010), Phys. Rev. Lett. 105, 231103
+        let earth = body("Earth")
+            .mass(EARTH_MASS)
+            .angular_momentum(EARTH_ANGULAR_MOMENTUM)
+            .build();
+        let non_rotating = body("Earth").mass(EARTH_MASS).build();
+        let lageos = Orbit::new(1.216_3e7, 0.014, 52.65_f64.to_radians());
+
+        let period = lageos.period(&earth).unwrap();
+        let per_year = |angle: f64| angle * JULIAN_YEAR / period * ARCSECONDS_PER_RADIAN * 1e3;
+        let perigee = per_year(lageos.periapsis_precession(&non_rotating).unwrap());
+        let frame_dragging = per_year(lageos.periapsis_precession(&earth).unwrap()) - perigee;
+
+        assert!((period / 60.0 - 222.5).abs() < 0.5);
+        assert!((perigee - 3348.0).abs() < 10.0, "{}", perigee);
+        assert!((per_year(lageos.nodal_precession(&earth).unwrap()) - 31.5).abs() < 0.5);
+        assert!((frame_dragging + 57.0).abs() < 1.0, "{}", frame_dragging);
+    }
+}
@@ -1,6 +1,8 @@
 
 extern crate test;
 
+pub mod orbits;
+
 const UNKNOWN: &'static str = "Unknown";
 const DEFAULT_DISCOVERED_YEAR: u16 = 2017;
 
@@ -87,7 +89,7 @@ pub trait DiscoveredByBuilder {
 
     /// Sets the name of the person who discovered the black hole. Returns a
     /// YearOfDiscoveryBuilder.
-    fn discovered_by(mut self, discovered_by: &str) -> YearOfDiscoveryBuilder;
+    fn discovered_by(self, discovered_by: &str) -> YearOfDiscoveryBuilder;
 }
 
 impl DiscoveredByBuilder for NameBuilder {
@@ -117,16 +119,16 @@ impl YearOfDiscoveryBuilder {
 pub trait BlackHoleBuilder {
     
     /// Sets the mass of the black hole
-    fn mass(mut self, mass: f64) -> Self;
+    fn mass(self, mass: f64) -> Self;
 
     /// Sets the angular momentum of the black hole
-    fn angular_momentum(mut self, angular_momentum: f64) -> Self;
+    fn angular_momentum(self, angular_momentum: f64) -> Self;
 
     /// Sets the electric charge of the black hole
-    fn electric_charge(mut self, electric_charge: f64) -> Self;
+    fn electric_charge(self, electric_charge: f64) -> Self;
 
     /// Sets the type of the black hole
-    fn classification(mut self, classification: Type) -> Self;
+    fn classification(self, classification: Type) -> Self;
 
     /// Build the black hole
     fn build(self) -> BlackHole;